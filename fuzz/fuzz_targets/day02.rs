@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2024::solve(2, 1, input);
+    let _ = aoc2024::solve(2, 2, input);
+});
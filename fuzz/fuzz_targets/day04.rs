@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = aoc2024::solve(4, 1, input);
+    let _ = aoc2024::solve(4, 2, input);
+});
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::fmt;
 
+use crate::puzzles;
 use crate::puzzles::unimplemented::UnimplementedPuzzle;
 
 pub trait Puzzle {
@@ -10,6 +12,77 @@ pub trait Puzzle {
     fn get_result_part_2(&self) -> Option<String>;
 }
 
+/// The answer to one part of a puzzle, exactly as it should be submitted.
+pub type Answer = String;
+
+pub type Result<T> = std::result::Result<T, SolveError>;
+
+/// Reasons why a puzzle part could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No puzzle is implemented for the requested day.
+    UnknownDay(u32),
+    /// Puzzles only have part 1 and part 2.
+    UnknownPart(u32),
+    /// The puzzle ran but did not produce a result for the given input.
+    NotSolved { day: u32, part: u32 },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownDay(day) => write!(f, "day {} is not implemented", day),
+            SolveError::UnknownPart(part) => write!(f, "part {} does not exist", part),
+            SolveError::NotSolved { day, part } => {
+                write!(f, "day {} part {} did not produce a result", day, part)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Runs one part of a puzzle on the given input and returns its answer.
+///
+/// A fresh puzzle instance is created for every call, so no state leaks
+/// between calls.
+///
+/// # Errors
+///
+/// Returns a [`SolveError`] if the day or part does not exist, or if the
+/// puzzle did not produce a result.
+pub fn solve(day: u32, part: u32, input: &str) -> Result<Answer> {
+    let mut puzzle = puzzles::create(day).ok_or(SolveError::UnknownDay(day))?;
+    solve_puzzle(puzzle.as_mut(), part, input)
+}
+
+/// Runs one part of an already created puzzle, e.g. one made with a different
+/// size than the puzzle input uses, and returns its answer.
+///
+/// # Errors
+///
+/// Returns a [`SolveError`] if the part does not exist, or if the puzzle did
+/// not produce a result.
+pub fn solve_puzzle(puzzle: &mut dyn Puzzle, part: u32, input: &str) -> Result<Answer> {
+    let result = match part {
+        1 => {
+            puzzle.execute_part_1(input);
+            puzzle.get_result_part_1()
+        }
+        2 => {
+            puzzle.execute_part_2(input);
+            puzzle.get_result_part_2()
+        }
+        _ => return Err(SolveError::UnknownPart(part)),
+    };
+
+    result.ok_or(SolveError::NotSolved {
+        day: puzzle.day(),
+        part,
+    })
+}
+
+#[derive(Default)]
 pub struct AdventOfCode {
     pub puzzles: HashMap<u32, Box<dyn Puzzle>>,
 }
@@ -21,6 +94,17 @@ impl AdventOfCode {
         }
     }
 
+    /// Creates a collection with every implemented puzzle registered.
+    pub fn with_all_puzzles() -> Self {
+        let mut aoc = Self::new();
+        for day in 1..=25 {
+            if let Some(puzzle) = puzzles::create(day) {
+                aoc.add_puzzle(day, puzzle);
+            }
+        }
+        aoc
+    }
+
     pub fn add_puzzle(&mut self, day: u32, puzzle: Box<dyn Puzzle>) {
         self.puzzles.insert(day, puzzle);
     }
@@ -31,4 +115,53 @@ impl AdventOfCode {
             .or_insert_with(|| Box::new(UnimplementedPuzzle::new(day)))
             .as_mut()
     }
+
+    /// Runs one part of a registered puzzle on the given input.
+    ///
+    /// # Errors
+    ///
+    /// See [`solve`].
+    pub fn solve(&mut self, day: u32, part: u32, input: &str) -> Result<Answer> {
+        let puzzle = self
+            .puzzles
+            .get_mut(&day)
+            .ok_or(SolveError::UnknownDay(day))?;
+        solve_puzzle(puzzle.as_mut(), part, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filehelper::read_test_input;
+
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = read_test_input(1, None, None);
+
+        assert_eq!(solve(1, 1, &input), Ok("11".to_string()));
+        assert_eq!(solve(1, 2, &input), Ok("31".to_string()));
+    }
+
+    #[test]
+    fn test_solve_unknown_day() {
+        assert_eq!(solve(26, 1, ""), Err(SolveError::UnknownDay(26)));
+    }
+
+    #[test]
+    fn test_solve_unknown_part() {
+        let input = read_test_input(1, None, None);
+
+        assert_eq!(solve(1, 3, &input), Err(SolveError::UnknownPart(3)));
+    }
+
+    #[test]
+    fn test_advent_of_code_solve() {
+        let mut aoc = AdventOfCode::with_all_puzzles();
+        let input = read_test_input(11, None, None);
+
+        assert_eq!(aoc.solve(11, 1, &input), Ok("55312".to_string()));
+        assert_eq!(aoc.solve(26, 1, &input), Err(SolveError::UnknownDay(26)));
+    }
 }
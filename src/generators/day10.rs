@@ -0,0 +1,56 @@
+use super::Rng;
+
+/// Generates a `size`x`size` topographic map with hiking trails.
+///
+/// The map starts out as random heights. Trails are then carved into it by
+/// walking from a trailhead at height 0 up to a summit at height 9, one step
+/// at a time. Later trails may cross and break earlier ones, but the last one
+/// is always intact, so every map has at least one trailhead with a score.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(4);
+    let mut map: Vec<Vec<u8>> = (0..size)
+        .map(|_| (0..size).map(|_| rng.below(10) as u8).collect())
+        .collect();
+
+    let trails = (size * size / 20).max(1);
+    let mut carved = 0;
+    while carved < trails {
+        if let Some(trail) = random_trail(size, rng) {
+            for (height, (x, y)) in trail.into_iter().enumerate() {
+                map[y][x] = height as u8;
+            }
+            carved += 1;
+        }
+    }
+
+    map.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|height| char::from_digit(height as u32, 10).unwrap())
+                .chain(std::iter::once('\n'))
+                .collect::<String>()
+        })
+        .collect()
+}
+
+/// Picks a random self-avoiding walk of ten cells, or `None` if the walk ran into itself.
+fn random_trail(size: usize, rng: &mut Rng) -> Option<Vec<(usize, usize)>> {
+    let mut trail = vec![(rng.below(size), rng.below(size))];
+
+    while trail.len() < 10 {
+        let (x, y) = *trail.last().unwrap();
+        let next = match rng.below(4) {
+            0 => (x, y.checked_sub(1)?),
+            1 => (x, y + 1),
+            2 => (x.checked_sub(1)?, y),
+            _ => (x + 1, y),
+        };
+
+        if next.0 >= size || next.1 >= size || trail.contains(&next) {
+            return None;
+        }
+        trail.push(next);
+    }
+
+    Some(trail)
+}
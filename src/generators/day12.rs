@@ -0,0 +1,51 @@
+use std::collections::VecDeque;
+
+use super::Rng;
+
+/// Generates a `size`x`size` garden of plant regions.
+///
+/// Random plots are picked as region seeds with a random plant type, and every
+/// other plot gets the plant of the seed closest to it. Neighbouring regions
+/// with the same plant type merge, just like in the real inputs.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(1);
+    let mut garden: Vec<Vec<Option<u8>>> = vec![vec![None; size]; size];
+    let mut queue = VecDeque::new();
+
+    let seeds = (size * size / 30).max(1);
+    for _ in 0..seeds {
+        let (x, y) = (rng.below(size), rng.below(size));
+        if garden[y][x].is_none() {
+            garden[y][x] = Some(b'A' + rng.below(26) as u8);
+            queue.push_back((x, y));
+        }
+    }
+
+    // Grow all regions at the same pace, which assigns every plot to its closest seed
+    while let Some((x, y)) = queue.pop_front() {
+        let plant = garden[y][x];
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+
+        for (nx, ny) in neighbours {
+            if nx < size && ny < size && garden[ny][nx].is_none() {
+                garden[ny][nx] = plant;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    garden
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|plant| plant.unwrap() as char)
+                .chain(std::iter::once('\n'))
+                .collect::<String>()
+        })
+        .collect()
+}
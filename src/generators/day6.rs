@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+/// Roughly the share of obstacles in the real puzzle inputs.
+const OBSTACLE_CHANCE: f64 = 0.05;
+
+/// Generates a `size`x`size` lab map with a guard that eventually walks off the map.
+///
+/// Maps where the guard gets stuck in a loop are thrown away and generated again.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(2);
+
+    loop {
+        let mut map: Vec<Vec<u8>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| {
+                        if rng.chance(OBSTACLE_CHANCE) {
+                            b'#'
+                        } else {
+                            b'.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let guard = (rng.below(size), rng.below(size));
+        map[guard.1][guard.0] = b'^';
+
+        if guard_exits(&map, guard) {
+            return map
+                .into_iter()
+                .map(|row| String::from_utf8(row).unwrap() + "\n")
+                .collect();
+        }
+    }
+}
+
+/// Walks the guard step by step and checks that it leaves the map.
+fn guard_exits(map: &[Vec<u8>], start: (usize, usize)) -> bool {
+    let size = map.len() as i64;
+    let directions = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+    let mut position = (start.0 as i64, start.1 as i64);
+    let mut direction = 0;
+    let mut states = HashSet::new();
+
+    while states.insert((position, direction)) {
+        let (dx, dy) = directions[direction];
+        let (x, y) = (position.0 + dx, position.1 + dy);
+        if x < 0 || x >= size || y < 0 || y >= size {
+            return true;
+        }
+
+        if map[y as usize][x as usize] == b'#' {
+            direction = (direction + 1) % 4;
+        } else {
+            position = (x, y);
+        }
+    }
+
+    false
+}
@@ -0,0 +1,24 @@
+use super::Rng;
+
+/// Generates a disk map with `size` digits, alternating between files of
+/// 1-9 blocks and free spaces of 0-9 blocks.
+///
+/// The disk map always starts and ends with a file, so an even `size` is
+/// rounded up.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(1) | 1;
+
+    let mut disk_map: String = (0..size)
+        .map(|index| {
+            let digit = if index % 2 == 0 {
+                1 + rng.below(9)
+            } else {
+                rng.below(10)
+            };
+            char::from_digit(digit as u32, 10).unwrap()
+        })
+        .collect();
+    disk_map.push('\n');
+
+    disk_map
+}
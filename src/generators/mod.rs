@@ -0,0 +1,91 @@
+//! Generators for valid puzzle inputs of configurable size.
+//!
+//! The generated inputs follow the same constraints as the real ones, so they
+//! can be used to benchmark and stress-test the solvers. All generators are
+//! driven by a seeded [`Rng`], so the same seed always gives the same input.
+
+pub mod day10;
+pub mod day12;
+pub mod day6;
+pub mod day9;
+
+/// A small, seedable pseudo-random number generator (SplitMix64).
+///
+/// Good enough for generating puzzle inputs, and it keeps the generated inputs
+/// stable across platforms and dependency upgrades.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..bound`. `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+}
+
+/// Generates an input for the given day, or `None` if there is no generator for it.
+///
+/// What `size` means depends on the day: the side length of the map for grid
+/// puzzles, and the number of digits for the disk map of day 9.
+pub fn generate(day: u32, size: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let input = match day {
+        6 => day6::generate(size, &mut rng),
+        9 => day9::generate(size, &mut rng),
+        10 => day10::generate(size, &mut rng),
+        12 => day12::generate(size, &mut rng),
+        _ => return None,
+    };
+    Some(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adventofcode::solve;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_is_reproducible() {
+        for day in [6, 9, 10, 12] {
+            assert_eq!(generate(day, 30, 7), generate(day, 30, 7));
+            assert_ne!(generate(day, 30, 7), generate(day, 30, 8));
+        }
+    }
+
+    #[test]
+    fn test_generate_unknown_day() {
+        assert_eq!(generate(1, 30, 7), None);
+    }
+
+    #[test]
+    fn test_generated_inputs_are_solvable() {
+        for day in [6, 9, 10, 12] {
+            for seed in 0..5 {
+                let input = generate(day, 25, seed).unwrap();
+
+                assert!(solve(day, 1, &input).is_ok(), "day {} seed {}", day, seed);
+                assert!(solve(day, 2, &input).is_ok(), "day {} seed {}", day, seed);
+            }
+        }
+    }
+}
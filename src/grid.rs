@@ -0,0 +1,40 @@
+//! Readers for the rectangular character maps that many puzzles use.
+
+/// A map stored row by row as a flat list of characters.
+pub struct Grid {
+    pub tiles: Vec<char>,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Reads a map, or `None` if it is empty or the rows differ in length.
+pub fn parse_grid(input: &str) -> Option<Grid> {
+    let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let width = rows.first()?.len();
+    if width == 0 || rows.iter().any(|row| row.len() != width) {
+        return None;
+    }
+
+    Some(Grid {
+        tiles: rows.concat(),
+        width: width as i32,
+        height: rows.len() as i32,
+    })
+}
+
+/// Reads a maze of walls `#` and open tiles `.` and returns it with the indexes
+/// of its start `S` and end `E`, or `None` if it is not rectangular, contains
+/// other characters or lacks exactly one start and end.
+pub fn parse_maze(input: &str) -> Option<(Grid, usize, usize)> {
+    let grid = parse_grid(input)?;
+    if grid.tiles.iter().any(|c| !"#.SE".contains(*c))
+        || grid.tiles.iter().filter(|&&c| c == 'S').count() != 1
+        || grid.tiles.iter().filter(|&&c| c == 'E').count() != 1
+    {
+        return None;
+    }
+
+    let start = grid.tiles.iter().position(|&c| c == 'S')?;
+    let end = grid.tiles.iter().position(|&c| c == 'E')?;
+    Some((grid, start, end))
+}
@@ -0,0 +1,18 @@
+//! Solutions for Advent of Code 2024.
+//!
+//! Every day is a [`Puzzle`] that can be driven directly, registered in an
+//! [`AdventOfCode`] collection, or solved in one go with [`solve`]:
+//!
+//! ```no_run
+//! let input = aoc2024::filehelper::read_input(1);
+//! let answer = aoc2024::solve(1, 1, &input).unwrap();
+//! println!("{}", answer);
+//! ```
+
+pub mod adventofcode;
+pub mod filehelper;
+pub mod generators;
+mod grid;
+pub mod puzzles;
+
+pub use adventofcode::{solve, solve_puzzle, AdventOfCode, Answer, Puzzle, Result, SolveError};
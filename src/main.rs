@@ -1,29 +1,154 @@
-mod adventofcode;
-mod filehelper;
-mod puzzles;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use std::time::Instant;
+use aoc2024::filehelper::read_input;
+use aoc2024::puzzles::{day14, day5, day7};
+use aoc2024::{generators, AdventOfCode};
 
-use adventofcode::AdventOfCode;
-use filehelper::read_input;
+const USAGE: &str = "\
+Usage:
+    aoc2024                                          Run all puzzles
+    aoc2024 generate --day N --size N [--seed N]     Print a generated input
+    aoc2024 explain --day N [--operators LIST]       Explain the answers for a day's input
 
-fn main() {
+Day 7 takes a comma separated list of operators to use instead of the
+puzzle's, e.g. `--operators +,*,-`. Available operators: +, *, ||, -, /, ^, **
+An equation may have at most 2^20 operator combinations, e.g. at most 8
+numbers with all 7 operators, otherwise there is no explanation.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        None => {
+            run_all_puzzles();
+            Ok(())
+        }
+        Some("generate") => parse_options(&args[1..]).and_then(|options| generate(&options)),
+        Some("explain") => parse_options(&args[1..]).and_then(|options| explain(&options)),
+        Some(command) => Err(format!("Unknown command '{}'", command)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parses `--name value` pairs into a map.
+fn parse_options(args: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut options = HashMap::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let name = arg
+            .strip_prefix("--")
+            .ok_or_else(|| format!("Unexpected argument '{}'", arg))?;
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for '--{}'", name))?;
+        options.insert(name.to_string(), value.clone());
+    }
+
+    Ok(options)
+}
+
+/// Reads a numeric option, or returns `None` if it was not given.
+fn numeric_option<T: std::str::FromStr>(
+    options: &HashMap<String, String>,
+    name: &str,
+) -> Result<Option<T>, String> {
+    options
+        .get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("Invalid value '{}' for '--{}'", value, name))
+        })
+        .transpose()
+}
+
+fn required_option<T: std::str::FromStr>(
+    options: &HashMap<String, String>,
+    name: &str,
+) -> Result<T, String> {
+    numeric_option(options, name)?.ok_or_else(|| format!("Missing option '--{}'", name))
+}
+
+fn generate(options: &HashMap<String, String>) -> Result<(), String> {
+    let day: u32 = required_option(options, "day")?;
+    let size: usize = required_option(options, "size")?;
+    let seed: u64 = numeric_option(options, "seed")?.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64)
+    });
+
+    let input = generators::generate(day, size, seed)
+        .ok_or_else(|| format!("There is no generator for day {}", day))?;
+    if !options.contains_key("seed") {
+        // Print the random seed so that the input can be reproduced
+        eprintln!("Seed: {}", seed);
+    }
+    print!("{}", input);
+
+    Ok(())
+}
+
+/// Fails if an option is given that the command does not take.
+fn allow_options(options: &HashMap<String, String>, allowed: &[&str]) -> Result<(), String> {
+    match options
+        .keys()
+        .find(|name| !allowed.contains(&name.as_str()))
+    {
+        Some(name) => Err(format!("Unknown option '--{}'", name)),
+        None => Ok(()),
+    }
+}
+
+fn explain(options: &HashMap<String, String>) -> Result<(), String> {
+    let day: u32 = required_option(options, "day")?;
+    allow_options(
+        options,
+        if day == 7 {
+            &["day", "operators"]
+        } else {
+            &["day"]
+        },
+    )?;
+
+    let explanation = match day {
+        5 => day5::explain(&read_input(day)),
+        14 => day14::explain(&read_input(day)),
+        7 => {
+            let operators = match options.get("operators") {
+                Some(symbols) => symbols
+                    .split(',')
+                    .map(|symbol| {
+                        day7::Operator::from_symbol(symbol.trim())
+                            .ok_or_else(|| format!("Unknown operator '{}'", symbol))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => day7::PART_2_OPERATORS.to_vec(),
+            };
+            day7::explain(&read_input(day), &operators)
+        }
+        _ => return Err(format!("There is no explanation for day {}", day)),
+    };
+    let explanation =
+        explanation.ok_or_else(|| format!("Failed to explain the input for day {}", day))?;
+    println!("{}", explanation);
+
+    Ok(())
+}
+
+fn run_all_puzzles() {
     let total_days = 25;
-    let mut aoc = AdventOfCode::new();
-
-    // Add a puzzles
-    aoc.add_puzzle(1, Box::new(puzzles::day1::Day1::new()));
-    aoc.add_puzzle(2, Box::new(puzzles::day2::Day2::new()));
-    aoc.add_puzzle(3, Box::new(puzzles::day3::Day3::new()));
-    aoc.add_puzzle(4, Box::new(puzzles::day4::Day4::new()));
-    aoc.add_puzzle(5, Box::new(puzzles::day5::Day5::new()));
-    aoc.add_puzzle(6, Box::new(puzzles::day6::Day6::new()));
-    aoc.add_puzzle(7, Box::new(puzzles::day7::Day7::new()));
-    aoc.add_puzzle(8, Box::new(puzzles::day8::Day8::new()));
-    aoc.add_puzzle(9, Box::new(puzzles::day9::Day9::new()));
-    aoc.add_puzzle(10, Box::new(puzzles::day10::Day10::new()));
-    aoc.add_puzzle(11, Box::new(puzzles::day11::Day11::new()));
-    aoc.add_puzzle(12, Box::new(puzzles::day12::Day12::new()));
+    let mut aoc = AdventOfCode::with_all_puzzles();
 
     // Define widths for table columns
     let day_width = 5;
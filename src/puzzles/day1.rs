@@ -1,5 +1,6 @@
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day1 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -14,19 +15,27 @@ impl Day1 {
     }
 }
 
+/// Splits the input lines into two vectors of integers, or `None` if a line is malformed.
+fn parse_input(input: &str) -> Option<(Vec<i32>, Vec<i32>)> {
+    let pairs = input
+        .lines()
+        .map(|item| {
+            let mut parts = item.split_whitespace();
+            Some((
+                parts.next()?.parse::<i32>().ok()?,
+                parts.next()?.parse::<i32>().ok()?,
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(pairs.into_iter().unzip())
+}
+
 impl Puzzle for Day1 {
     fn execute_part_1(&mut self, input: &str) {
-        // Split the input lines into two vectors of integers
-        let (mut left_values, mut right_values): (Vec<i32>, Vec<i32>) = input
-            .lines()
-            .map(|item| {
-                let mut parts = item.split_whitespace();
-                (
-                    parts.next().unwrap().parse::<i32>().unwrap(),
-                    parts.next().unwrap().parse::<i32>().unwrap(),
-                )
-            })
-            .unzip();
+        let Some((mut left_values, mut right_values)) = parse_input(input) else {
+            return;
+        };
 
         // Sort the left and right values independently
         left_values.sort();
@@ -36,31 +45,25 @@ impl Puzzle for Day1 {
         let total_distance = left_values
             .iter()
             .zip(right_values.iter())
-            .map(|(left, right)| ((left - right).abs()))
-            .sum::<i32>();
+            .map(|(&left, &right)| (i64::from(left) - i64::from(right)).abs())
+            .sum::<i64>();
 
         self.part_1_result = Some(total_distance.to_string());
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        // Split the input lines into two vectors of integers
-        let (left_values, right_values): (Vec<i32>, Vec<i32>) = input
-            .lines()
-            .map(|item| {
-                let mut parts = item.split_whitespace();
-                (
-                    parts.next().unwrap().parse::<i32>().unwrap(),
-                    parts.next().unwrap().parse::<i32>().unwrap(),
-                )
-            })
-            .unzip();
+        let Some((left_values, right_values)) = parse_input(input) else {
+            return;
+        };
 
         // Calculate the similarity score by summing up the products
         // of each left value and its occurrences in the right values
         let similarity_score = left_values
             .iter()
-            .map(|&value| right_values.iter().filter(|&&x| x == value).count() as i32 * value)
-            .sum::<i32>();
+            .map(|&value| {
+                right_values.iter().filter(|&&x| x == value).count() as i64 * i64::from(value)
+            })
+            .sum::<i64>();
 
         self.part_2_result = Some(similarity_score.to_string());
     }
@@ -1,7 +1,7 @@
-use std::collections::HashSet;
-
 use crate::adventofcode::Puzzle;
+use crate::grid::parse_grid;
 
+#[derive(Default)]
 pub struct Day10 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -16,119 +16,142 @@ impl Day10 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Direction {
-    N,
-    S,
-    W,
-    E,
-}
+/// The height of a summit, where every hiking trail ends.
+const SUMMIT: usize = 9;
 
-impl Direction {
-    fn coordinates(&self) -> (i32, i32) {
-        match self {
-            Direction::N => (0, -1),
-            Direction::S => (0, 1),
-            Direction::W => (-1, 0),
-            Direction::E => (1, 0),
-        }
-    }
+/// The topographic map, with every cell referred to by its index `y * width + x`.
+struct TopographicMap {
+    width: usize,
+    heights: Vec<Option<usize>>,
+    /// The cells of every height from 0 to 9, so the map can be processed
+    /// one height at a time.
+    layers: [Vec<usize>; SUMMIT + 1],
 }
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<(i32, i32)>) {
-    let width = input.lines().next().unwrap().len() as i32;
-    let map: Vec<u32> = input
-        .lines()
-        .flat_map(|line| line.chars().map(|c| c.to_digit(10).unwrap_or(u32::MAX)))
-        .collect();
-    let starting_pos: Vec<(i32, i32)> = map
+/// Reads the height map, or `None` if it is empty or not rectangular.
+/// Cells that are not digits are impassable.
+fn parse_input(input: &str) -> Option<TopographicMap> {
+    let grid = parse_grid(input)?;
+    let heights: Vec<Option<usize>> = grid
+        .tiles
         .iter()
-        .enumerate()
-        .filter(|(_, &number)| number == 0)
-        .map(|(index, _)| {
-            let x = index as i32 % width;
-            let y = index as i32 / width;
-            (x, y)
-        })
+        .map(|c| c.to_digit(10).map(|h| h as usize))
         .collect();
+    let mut layers: [Vec<usize>; SUMMIT + 1] = Default::default();
+    for (cell, height) in heights.iter().enumerate() {
+        if let Some(height) = *height {
+            layers[height].push(cell);
+        }
+    }
 
-    (map, starting_pos)
+    Some(TopographicMap {
+        width: grid.width as usize,
+        heights,
+        layers,
+    })
 }
 
-fn find_all_paths(
-    map: &[u32],
-    width: i32,
-    height: i32,
-    start_pos: (i32, i32),
-) -> HashSet<Vec<(i32, i32)>> {
-    let mut all_paths = HashSet::new();
-    let mut stack = vec![(vec![start_pos], start_pos)]; // Current path, and current position)
-
-    while let Some((path, (x, y))) = stack.pop() {
-        let this_index = x as usize + width as usize * y as usize;
-
-        if map[this_index] == 9 {
-            // Found a valid path!
-            all_paths.insert(path.clone());
-            continue;
-        }
+impl TopographicMap {
+    /// The neighbouring cells of `cell` that are at the given height.
+    fn neighbours(&self, cell: usize, height: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = (cell % self.width, cell / self.width);
+        let left = (x > 0).then(|| cell - 1);
+        let right = (x + 1 < self.width).then_some(cell + 1);
+        let up = (y > 0).then(|| cell - self.width);
+        let down = Some(cell + self.width).filter(|&below| below < self.heights.len());
+
+        [left, right, up, down]
+            .into_iter()
+            .flatten()
+            .filter(move |&neighbour| self.heights[neighbour] == Some(height))
+    }
 
-        for &dir in &[Direction::N, Direction::S, Direction::W, Direction::E] {
-            let new_x = x + dir.coordinates().0;
-            let new_y = y + dir.coordinates().1;
+    /// Sums the number of distinct summits every trailhead can reach.
+    ///
+    /// Walks down from the summits one height at a time, so every cell collects
+    /// the summits of its uphill neighbours. The reachable summits are kept as
+    /// bitsets, and to keep them at a single `u64` per cell the summits are
+    /// handled 64 at a time. Each batch only visits the cells below its own
+    /// summits, so a cell is only visited by the few batches that reach it.
+    fn score_sum(&self) -> usize {
+        let mut reachable = vec![0u64; self.heights.len()];
+        let mut result = 0;
 
-            if new_x >= 0 && new_x < width && new_y >= 0 && new_y < height {
-                let new_pos = (new_x, new_y);
-                let index = new_x as usize + width as usize * new_y as usize;
-                let next_valid_step = map[this_index] + 1;
+        for summits in self.layers[SUMMIT].chunks(64) {
+            for (bit, &summit) in summits.iter().enumerate() {
+                reachable[summit] = 1 << bit;
+            }
 
-                if next_valid_step != map[index] {
-                    continue;
+            let mut visited = Vec::new();
+            let mut frontier = summits.to_vec();
+            for height in (0..SUMMIT).rev() {
+                let mut below = Vec::new();
+                for &cell in &frontier {
+                    for neighbour in self.neighbours(cell, height) {
+                        if reachable[neighbour] == 0 {
+                            below.push(neighbour);
+                        }
+                        reachable[neighbour] |= reachable[cell];
+                    }
                 }
+                visited.append(&mut frontier);
+                frontier = below;
+            }
 
-                if map[index] == next_valid_step && !path.contains(&new_pos) {
-                    let mut new_path = path.clone();
-                    new_path.push(new_pos);
-                    stack.push((new_path, new_pos));
-                }
+            result += frontier
+                .iter()
+                .map(|&trailhead| reachable[trailhead].count_ones() as usize)
+                .sum::<usize>();
+
+            for cell in visited.into_iter().chain(frontier) {
+                reachable[cell] = 0;
             }
         }
+
+        result
     }
 
-    all_paths
+    /// Sums the number of distinct hiking trails that start at every trailhead.
+    ///
+    /// Works down from the summits one height at a time, so the rating of
+    /// every cell is the sum of the ratings of its uphill neighbours.
+    fn rating_sum(&self) -> u64 {
+        let mut ratings = vec![0u64; self.heights.len()];
+        for &summit in &self.layers[SUMMIT] {
+            ratings[summit] = 1;
+        }
+
+        for height in (0..SUMMIT).rev() {
+            for &cell in &self.layers[height] {
+                ratings[cell] = self
+                    .neighbours(cell, height + 1)
+                    .map(|neighbour| ratings[neighbour])
+                    .sum();
+            }
+        }
+
+        self.layers[0]
+            .iter()
+            .map(|&trailhead| ratings[trailhead])
+            .sum()
+    }
 }
 
 impl Puzzle for Day10 {
     fn execute_part_1(&mut self, input: &str) {
-        let mut result = 0;
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let (map, starting_pos) = parse_input(input);
+        let Some(map) = parse_input(input) else {
+            return;
+        };
 
-        for start in &starting_pos {
-            let all_paths = find_all_paths(&map, width, height, *start);
-
-            let unique_paths: HashSet<(i32, i32)> =
-                all_paths.iter().map(|path| *path.last().unwrap()).collect();
-
-            result += unique_paths.len();
-        }
-
-        self.part_1_result = Some(result.to_string());
+        self.part_1_result = Some(map.score_sum().to_string());
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let mut result = 0;
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let (map, starting_pos) = parse_input(input);
-
-        for start in &starting_pos {
-            let all_paths = find_all_paths(&map, width, height, *start);
-            result += all_paths.len();
-        }
+        let Some(map) = parse_input(input) else {
+            return;
+        };
 
-        self.part_2_result = Some(result.to_string());
+        self.part_2_result = Some(map.rating_sum().to_string());
     }
 
     fn get_result_part_1(&self) -> Option<String> {
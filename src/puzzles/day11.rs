@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day11 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -16,12 +17,13 @@ impl Day11 {
     }
 }
 
-fn calc_stones(input: &str, steps: usize) -> u64 {
+/// Counts the stones after blinking `steps` times, or returns `None` if the input is
+/// malformed or a stone's number no longer fits in a `u64`.
+fn calc_stones(input: &str, steps: usize) -> Option<u64> {
     let mut starting_stones = HashMap::new();
-    input
-        .split_ascii_whitespace()
-        .map(|x| x.parse::<u64>().unwrap())
-        .for_each(|num| *starting_stones.entry(num).or_insert(0) += 1);
+    for num in input.split_ascii_whitespace() {
+        *starting_stones.entry(num.parse::<u64>().ok()?).or_insert(0) += 1;
+    }
 
     let mut starting_stones_clone = starting_stones.clone();
     for _ in 0..steps {
@@ -54,23 +56,23 @@ fn calc_stones(input: &str, steps: usize) -> u64 {
             } else {
                 // If none of the other rules apply, the stone is replaced by a new stone;
                 // the old stone's number multiplied by 2024 is engraved on the new stone.
-                *tmp.entry(value * 2024).or_insert(0) += occurences;
+                *tmp.entry(value.checked_mul(2024)?).or_insert(0) += occurences;
             }
         }
 
         starting_stones_clone = tmp.clone();
     }
 
-    starting_stones_clone.values().sum()
+    Some(starting_stones_clone.values().sum())
 }
 
 impl Puzzle for Day11 {
     fn execute_part_1(&mut self, input: &str) {
-        self.part_1_result = Some(calc_stones(input, 25).to_string());
+        self.part_1_result = calc_stones(input, 25).map(|x| x.to_string());
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        self.part_2_result = Some(calc_stones(input, 75).to_string());
+        self.part_2_result = calc_stones(input, 75).map(|x| x.to_string());
     }
 
     fn get_result_part_1(&self) -> Option<String> {
@@ -1,7 +1,9 @@
 use std::collections::{HashSet, VecDeque};
 
 use crate::adventofcode::Puzzle;
+use crate::grid::{parse_grid, Grid};
 
+#[derive(Default)]
 pub struct Day12 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -35,64 +37,136 @@ impl Direction {
     }
 }
 
-impl Puzzle for Day12 {
-    fn execute_part_1(&mut self, input: &str) {
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let map: Vec<char> = input
-            .lines()
-            .flat_map(|line| line.chars())
-            .collect();
-
-        let mut map_copy = map.clone();
-        let mut result = 0;
+/// Groups the plots into regions of connected plots with the same plant, using a flood fill.
+fn find_regions(map: &[char], width: i32, height: i32) -> Vec<HashSet<usize>> {
+    let mut regions = Vec::new();
+    let mut ignore_indexes: HashSet<usize> = HashSet::new();
 
-        let mut ignore_indexes: HashSet<usize> = HashSet::new();
-
-        for (i, c) in map_copy.iter_mut().enumerate() {
-            if ignore_indexes.contains(&i) {
+    for (i, &target) in map.iter().enumerate() {
+        if ignore_indexes.contains(&i) {
+            continue;
+        }
+        let mut queue = VecDeque::new();
+        let mut visited: HashSet<usize> = HashSet::new();
+        queue.push_back(i);
+        while let Some(index) = queue.pop_front() {
+            if visited.contains(&index) {
                 continue;
             }
-            let target = *c;
-            let mut perimeter = 0;
-            let mut queue = VecDeque::new();
-            let mut visited: HashSet<usize> = HashSet::new();
-            queue.push_back(i);
-            //visited.insert(i);
-            while let Some(index) = queue.pop_front() {
-                if visited.contains(&index) {
-                    continue;
-                }
-                visited.insert(index);
-
-                let x = index as i32 % width;
-                let y = index as i32 / width;
-
-                for &dir in &[Direction::N, Direction::S, Direction::W, Direction::E] {
-                    let new_x = x + dir.coordinates().0;
-                    let new_y = y + dir.coordinates().1;
-
-                    if new_x >= 0 && new_x < width && new_y >= 0 && new_y < height {
-                        let n_index = new_x as usize + width as usize * new_y as usize;
-                        if *map.get(n_index).unwrap() != target && !visited.contains(&n_index) {
-                            perimeter += 1;
-                        } else if !visited.contains(&n_index) {
-                            queue.push_back(n_index);
-                        }
-                    } else {
-                        perimeter += 1;
+            visited.insert(index);
+
+            for &dir in &[Direction::N, Direction::S, Direction::W, Direction::E] {
+                if let Some(n_index) = neighbour(index, dir.coordinates(), width, height) {
+                    if map[n_index] == target && !visited.contains(&n_index) {
+                        queue.push_back(n_index);
                     }
                 }
             }
-            ignore_indexes.extend(visited.clone());
-            result += perimeter * visited.len();
         }
+        ignore_indexes.extend(visited.iter());
+        regions.push(visited);
+    }
+
+    regions
+}
+
+/// Returns the index of the plot at the given offset, or `None` if it is outside the map.
+fn neighbour(index: usize, (dx, dy): (i32, i32), width: i32, height: i32) -> Option<usize> {
+    let new_x = index as i32 % width + dx;
+    let new_y = index as i32 / width + dy;
+
+    if new_x >= 0 && new_x < width && new_y >= 0 && new_y < height {
+        Some(new_x as usize + width as usize * new_y as usize)
+    } else {
+        None
+    }
+}
+
+fn perimeter(region: &HashSet<usize>, width: i32, height: i32) -> usize {
+    region
+        .iter()
+        .map(|&index| {
+            [Direction::N, Direction::S, Direction::W, Direction::E]
+                .iter()
+                .filter(|dir| {
+                    neighbour(index, dir.coordinates(), width, height)
+                        .is_none_or(|n_index| !region.contains(&n_index))
+                })
+                .count()
+        })
+        .sum()
+}
+
+/// Counts the straight sides of a region, which is the same as counting its corners.
+///
+/// Every plot is checked at its four corners. A corner is convex when both
+/// neighbouring plots along the corner are outside the region, and concave
+/// when both are inside but the diagonal plot between them is outside.
+fn sides(region: &HashSet<usize>, width: i32, height: i32) -> usize {
+    let in_region = |index: usize, offset: (i32, i32)| {
+        neighbour(index, offset, width, height).is_some_and(|n_index| region.contains(&n_index))
+    };
+    let corners = [
+        (Direction::N, Direction::E),
+        (Direction::E, Direction::S),
+        (Direction::S, Direction::W),
+        (Direction::W, Direction::N),
+    ];
+
+    region
+        .iter()
+        .map(|&index| {
+            corners
+                .iter()
+                .filter(|(a, b)| {
+                    let (a, b) = (a.coordinates(), b.coordinates());
+                    let diagonal = (a.0 + b.0, a.1 + b.1);
+
+                    let convex = !in_region(index, a) && !in_region(index, b);
+                    let concave =
+                        in_region(index, a) && in_region(index, b) && !in_region(index, diagonal);
+                    convex || concave
+                })
+                .count()
+        })
+        .sum()
+}
+
+impl Puzzle for Day12 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(Grid {
+            tiles: map,
+            width,
+            height,
+        }) = parse_grid(input)
+        else {
+            return;
+        };
+
+        let result: usize = find_regions(&map, width, height)
+            .iter()
+            .map(|region| region.len() * perimeter(region, width, height))
+            .sum();
 
         self.part_1_result = Some(result.to_string());
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        self.part_2_result = Some("0".to_string());
+        let Some(Grid {
+            tiles: map,
+            width,
+            height,
+        }) = parse_grid(input)
+        else {
+            return;
+        };
+
+        let result: usize = find_regions(&map, width, height)
+            .iter()
+            .map(|region| region.len() * sides(region, width, height))
+            .sum();
+
+        self.part_2_result = Some(result.to_string());
     }
 
     fn get_result_part_1(&self) -> Option<String> {
@@ -159,24 +233,57 @@ mod tests {
     }
 
     #[test]
-    fn test_day12_part_2() {
+    fn test_day12_part_2_1() {
         let mut puzzle = Day12::new();
         let input = read_test_input(puzzle.day(), None, Some(1));
 
         puzzle.execute_part_2(&input);
 
         let result = puzzle.get_result_part_2();
-        assert_eq!(result, Some("0".to_string()));
+        assert_eq!(result, Some("80".to_string()));
     }
 
     #[test]
-    fn test_day12_part_2_real() {
+    fn test_day12_part_2_2() {
         let mut puzzle = Day12::new();
-        let input = read_input(puzzle.day());
+        let input = read_test_input(puzzle.day(), None, Some(2));
+
+        puzzle.execute_part_2(&input);
+
+        let result = puzzle.get_result_part_2();
+        assert_eq!(result, Some("436".to_string()));
+    }
+
+    #[test]
+    fn test_day12_part_2_3() {
+        let mut puzzle = Day12::new();
+        let input = read_test_input(puzzle.day(), None, Some(3));
+
+        puzzle.execute_part_2(&input);
+
+        let result = puzzle.get_result_part_2();
+        assert_eq!(result, Some("1206".to_string()));
+    }
+
+    #[test]
+    fn test_day12_part_2_4() {
+        let mut puzzle = Day12::new();
+        let input = read_test_input(puzzle.day(), None, Some(4));
+
+        puzzle.execute_part_2(&input);
+
+        let result = puzzle.get_result_part_2();
+        assert_eq!(result, Some("236".to_string()));
+    }
+
+    #[test]
+    fn test_day12_part_2_5() {
+        let mut puzzle = Day12::new();
+        let input = read_test_input(puzzle.day(), None, Some(5));
 
         puzzle.execute_part_2(&input);
 
         let result = puzzle.get_result_part_2();
-        assert_eq!(result, Some("0".to_string()));
+        assert_eq!(result, Some("368".to_string()));
     }
 }
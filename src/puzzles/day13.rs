@@ -0,0 +1,267 @@
+use regex::Regex;
+
+use crate::adventofcode::Puzzle;
+
+#[derive(Default)]
+pub struct Day13 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day13 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+const A_COST: i128 = 3;
+const B_COST: i128 = 1;
+const PRIZE_OFFSET: i128 = 10_000_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ClawMachine {
+    a: (i128, i128),
+    b: (i128, i128),
+    prize: (i128, i128),
+}
+
+impl ClawMachine {
+    /// Returns the fewest tokens needed to win the prize, or `None` if it cannot be won.
+    ///
+    /// Pressing A `a` times and B `b` times is a 2x2 linear system, which is solved
+    /// exactly with Cramer's rule. Only non-negative integer solutions count.
+    fn tokens(&self) -> Option<i128> {
+        let (ax, ay) = self.a;
+        let (bx, by) = self.b;
+        let (px, py) = self.prize;
+
+        let det = ax * by - ay * bx;
+        if det == 0 {
+            return self.tokens_collinear();
+        }
+
+        let a_numerator = px * by - py * bx;
+        let b_numerator = ax * py - ay * px;
+        if a_numerator % det != 0 || b_numerator % det != 0 {
+            return None;
+        }
+
+        let a = a_numerator / det;
+        let b = b_numerator / det;
+        if a < 0 || b < 0 {
+            return None;
+        }
+
+        a.checked_mul(A_COST)?.checked_add(b.checked_mul(B_COST)?)
+    }
+
+    /// Handles buttons that move the claw along the same line. The system then has
+    /// either no solution or a whole family of them, and the cheapest one is picked.
+    fn tokens_collinear(&self) -> Option<i128> {
+        let (ax, ay) = self.a;
+        let (bx, by) = self.b;
+        let (px, py) = self.prize;
+
+        // The prize must lie on the same line as the buttons
+        if ax * py - ay * px != 0 || bx * py - by * px != 0 {
+            return None;
+        }
+
+        // Solve along the axis the line actually moves in
+        if ax != 0 || bx != 0 {
+            cheapest_presses(ax, bx, px)
+        } else {
+            cheapest_presses(ay, by, py)
+        }
+    }
+}
+
+/// Finds the cheapest non-negative `a` and `b` with `a * u + b * v == target`.
+fn cheapest_presses(u: i128, v: i128, target: i128) -> Option<i128> {
+    match (u, v) {
+        (0, 0) => (target == 0).then_some(0),
+        (0, v) => (target % v == 0).then_some(target / v * B_COST),
+        (u, 0) => (target % u == 0).then_some(target / u * A_COST),
+        (u, v) => {
+            let (g, x, y) = extended_gcd(u, v);
+            if target % g != 0 {
+                return None;
+            }
+
+            // All solutions are a = a0 + k * s and b = b0 - k * r
+            let a0 = x.checked_mul(target / g)?;
+            let b0 = y.checked_mul(target / g)?;
+            let (s, r) = (v / g, u / g);
+
+            let k_min = -a0.div_euclid(s); // a >= 0
+            let k_max = b0.div_euclid(r); // b >= 0
+            if k_min > k_max {
+                return None;
+            }
+
+            // The cost changes linearly with k, so the cheapest solution is at one end
+            let k = if A_COST * s - B_COST * r > 0 {
+                k_min
+            } else {
+                k_max
+            };
+            let a = a0.checked_add(k.checked_mul(s)?)?;
+            let b = b0.checked_sub(k.checked_mul(r)?)?;
+
+            a.checked_mul(A_COST)?.checked_add(b.checked_mul(B_COST)?)
+        }
+    }
+}
+
+/// Returns `(g, x, y)` such that `u * x + v * y == g == gcd(u, v)`.
+fn extended_gcd(u: i128, v: i128) -> (i128, i128, i128) {
+    if v == 0 {
+        (u, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(v, u % v);
+        (g, y, x - (u / v) * y)
+    }
+}
+
+/// Reads the claw machines, three lines each, or returns `None` if any of the
+/// lines does not describe a claw machine.
+fn parse_input(input: &str) -> Option<Vec<ClawMachine>> {
+    let pattern =
+        r"^Button A: X\+(\d+), Y\+(\d+)\nButton B: X\+(\d+), Y\+(\d+)\nPrize: X=(\d+), Y=(\d+)$";
+    let re = Regex::new(pattern).unwrap();
+
+    let lines: Vec<&str> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if !lines.len().is_multiple_of(3) {
+        return None;
+    }
+
+    lines
+        .chunks(3)
+        .map(|machine| {
+            let machine = machine.join("\n");
+            let x = re.captures(&machine)?;
+            // Values fit in an i64, so no product of two of them overflows an i128
+            let value = |i: usize| x[i].parse::<i64>().ok().map(i128::from);
+            Some(ClawMachine {
+                a: (value(1)?, value(2)?),
+                b: (value(3)?, value(4)?),
+                prize: (value(5)?, value(6)?),
+            })
+        })
+        .collect()
+}
+
+fn total_tokens(machines: &[ClawMachine]) -> i128 {
+    machines.iter().filter_map(ClawMachine::tokens).sum()
+}
+
+impl Puzzle for Day13 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(machines) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = Some(total_tokens(&machines).to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(machines) = parse_input(input) else {
+            return;
+        };
+
+        // The prizes are actually much further away
+        let machines: Vec<ClawMachine> = machines
+            .into_iter()
+            .map(|machine| ClawMachine {
+                prize: (
+                    machine.prize.0 + PRIZE_OFFSET,
+                    machine.prize.1 + PRIZE_OFFSET,
+                ),
+                ..machine
+            })
+            .collect();
+
+        self.part_2_result = Some(total_tokens(&machines).to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        13
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day13_collinear_buttons() {
+        let machine = ClawMachine {
+            a: (1, 1),
+            b: (3, 3),
+            prize: (10, 10),
+        };
+
+        // One A press and three B presses is cheaper than any other combination
+        assert_eq!(machine.tokens(), Some(6));
+
+        let machine = ClawMachine {
+            a: (9, 9),
+            b: (1, 1),
+            prize: (10, 10),
+        };
+        assert_eq!(machine.tokens(), Some(4));
+
+        let machine = ClawMachine {
+            a: (2, 2),
+            b: (4, 4),
+            prize: (7, 7),
+        };
+        assert_eq!(machine.tokens(), None);
+    }
+
+    #[test]
+    fn test_day13_malformed_input() {
+        let machine = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400";
+
+        assert!(parse_input(machine).is_some());
+        assert_eq!(parse_input(&format!("{}\nPrize: X=1, Y=1", machine)), None);
+        assert_eq!(parse_input(&machine.replace("Y+67", "Y-67")), None);
+        assert_eq!(
+            parse_input(&format!("{}\n\nButton A: X+1, Y+1", machine)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_day13_huge_values() {
+        let max = i64::MAX as i128;
+        let machine = ClawMachine {
+            a: (1, 0),
+            b: (max, 1),
+            prize: (max * max, 0),
+        };
+        assert_eq!(machine.tokens(), None);
+
+        let machine = ClawMachine {
+            a: (max, max),
+            b: (max - 1, max),
+            prize: (max, max),
+        };
+        assert_eq!(machine.tokens(), Some(3));
+    }
+}
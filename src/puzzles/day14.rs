@@ -0,0 +1,301 @@
+use regex::Regex;
+
+use crate::adventofcode::Puzzle;
+
+pub struct Day14 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+    width: i64,
+    height: i64,
+}
+
+impl Default for Day14 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Day14 {
+    pub fn new() -> Self {
+        Self::with_size(101, 103)
+    }
+
+    /// Creates the puzzle for a bathroom of a different size, e.g. 11x7 for the example.
+    /// Both sizes must be positive. Part 2 is only guaranteed to find the picture
+    /// if the sizes have no common factor, like the 101x103 of the puzzle.
+    pub fn with_size(width: i64, height: i64) -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+            width,
+            height,
+        }
+    }
+
+    /// Renders the robots after the given number of seconds, one `#` per occupied tile.
+    pub fn render(&self, input: &str, seconds: i64) -> Option<String> {
+        let robots = parse_input(input)?;
+        let mut grid = vec![vec!['.'; self.width as usize]; self.height as usize];
+
+        for robot in &robots {
+            let (x, y) = robot.position_after(seconds, self.width, self.height);
+            grid[y as usize][x as usize] = '#';
+        }
+
+        Some(
+            grid.iter()
+                .map(|row| row.iter().collect::<String>() + "\n")
+                .collect(),
+        )
+    }
+
+    fn safety_factor(&self, robots: &[Robot], seconds: i64) -> usize {
+        let mid_x = self.width / 2;
+        let mid_y = self.height / 2;
+        let mut quadrants = [0; 4];
+
+        for robot in robots {
+            let (x, y) = robot.position_after(seconds, self.width, self.height);
+            if x == mid_x || y == mid_y {
+                // Robots exactly in the middle don't count
+                continue;
+            }
+
+            let quadrant = usize::from(x > mid_x) + 2 * usize::from(y > mid_y);
+            quadrants[quadrant] += 1;
+        }
+
+        quadrants.iter().product()
+    }
+
+    /// Finds the first second at which the robots arrange into a picture, or
+    /// `None` if the input cannot be parsed or no such second is found.
+    pub fn find_picture(&self, input: &str) -> Option<i64> {
+        self.picture_second(&parse_input(input)?)
+    }
+
+    /// The x coordinates repeat every `width` seconds and the y coordinates every
+    /// `height` seconds. When the picture appears most robots are bunched together,
+    /// so the spread (variance) of each coordinate is at its lowest. Finding the
+    /// best offset for each axis separately and combining them with the Chinese
+    /// remainder theorem gives the second where both happen at once.
+    ///
+    /// The robots repeat after the least common multiple of both sizes. If the
+    /// sizes share a factor, the two offsets may never line up within that
+    /// period, and there is no picture.
+    fn picture_second(&self, robots: &[Robot]) -> Option<i64> {
+        let best_x = (0..self.width).min_by_key(|&seconds| {
+            variance(
+                robots
+                    .iter()
+                    .map(|robot| robot.position_after(seconds, self.width, self.height).0),
+            )
+        })?;
+        let best_y = (0..self.height).min_by_key(|&seconds| {
+            variance(
+                robots
+                    .iter()
+                    .map(|robot| robot.position_after(seconds, self.width, self.height).1),
+            )
+        })?;
+
+        let period = self.height / gcd(self.width, self.height);
+        (0..period)
+            .map(|k| best_x + k * self.width)
+            .find(|seconds| seconds % self.height == best_y)
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Finds the second the robots in the puzzle input arrange into a picture and
+/// renders that frame. Returns `None` if the input cannot be parsed or there
+/// is no picture.
+pub fn explain(input: &str) -> Option<String> {
+    let puzzle = Day14::new();
+    let seconds = puzzle.find_picture(input)?;
+
+    Some(format!(
+        "Picture after {} seconds:\n{}",
+        seconds,
+        puzzle.render(input, seconds)?
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Robot {
+    position: (i64, i64),
+    velocity: (i64, i64),
+}
+
+impl Robot {
+    /// The robots teleport to the other side at the edges, so the bathroom is a torus.
+    fn position_after(&self, seconds: i64, width: i64, height: i64) -> (i64, i64) {
+        let x = (self.position.0 + self.velocity.0 * seconds).rem_euclid(width);
+        let y = (self.position.1 + self.velocity.1 * seconds).rem_euclid(height);
+        (x, y)
+    }
+}
+
+/// Returns the variance of the values, scaled by the number of values squared so
+/// that it stays an exact integer.
+fn variance(values: impl Iterator<Item = i64>) -> i64 {
+    let (count, sum, sum_of_squares) = values.fold((0, 0, 0), |(count, sum, squares), value| {
+        (count + 1, sum + value, squares + value * value)
+    });
+    count * sum_of_squares - sum * sum
+}
+
+/// Reads one robot per line, or returns `None` if a line is not a robot or there are
+/// no robots at all.
+fn parse_input(input: &str) -> Option<Vec<Robot>> {
+    let pattern = r"^p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)$";
+    let re = Regex::new(pattern).unwrap();
+
+    let robots: Vec<Robot> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let x = re.captures(line)?;
+            // Keep the values small enough that the simulation cannot overflow
+            let value = |i: usize| x[i].parse::<i32>().ok().map(i64::from);
+            Some(Robot {
+                position: (value(1)?, value(2)?),
+                velocity: (value(3)?, value(4)?),
+            })
+        })
+        .collect::<Option<_>>()?;
+
+    (!robots.is_empty()).then_some(robots)
+}
+
+impl Puzzle for Day14 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(robots) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = Some(self.safety_factor(&robots, 100).to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(robots) = parse_input(input) else {
+            return;
+        };
+
+        self.part_2_result = self.picture_second(&robots).map(|x| x.to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        14
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filehelper::read_test_input;
+
+    use super::*;
+
+    /// Builds robots that form a filled square at `seconds`, plus some scattered robots.
+    fn picture_input(seconds: i64) -> String {
+        let (width, height) = (101, 103);
+        let mut state: i64 = 12345;
+        let mut random = |bound: i64| {
+            state = (state * 1_103_515_245 + 12345) % 2_147_483_648;
+            state % bound
+        };
+
+        let picture = (40..55).flat_map(|y| (45..60).map(move |x| (x, y)));
+        let noise: Vec<(i64, i64)> = (0..100).map(|_| (random(width), random(height))).collect();
+
+        picture
+            .chain(noise)
+            .map(|(x, y)| {
+                let (vx, vy) = (random(201) - 100, random(201) - 100);
+                let start_x = (x - vx * seconds).rem_euclid(width);
+                let start_y = (y - vy * seconds).rem_euclid(height);
+                format!("p={},{} v={},{}\n", start_x, start_y, vx, vy)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_day14_part_2() {
+        let mut puzzle = Day14::new();
+        let input = picture_input(6789);
+
+        puzzle.execute_part_2(&input);
+
+        let result = puzzle.get_result_part_2();
+        assert_eq!(result, Some("6789".to_string()));
+    }
+
+    #[test]
+    fn test_day14_part_2_sizes_with_common_factor() {
+        let puzzle = Day14::with_size(4, 6);
+        // The x coordinates meet after 1 and 3 seconds, the y coordinates after
+        // 2 and 5 seconds, which never happen at once within the period of 12 seconds
+        let input = "p=0,0 v=1,1\np=2,4 v=-1,-1";
+
+        assert_eq!(puzzle.find_picture(input), None);
+    }
+
+    #[test]
+    fn test_day14_explain() {
+        let input = picture_input(6789);
+
+        let explanation = explain(&input).unwrap();
+
+        assert!(explanation.starts_with("Picture after 6789 seconds:\n"));
+        assert!(explanation.contains(&"#".repeat(15)));
+    }
+
+    #[test]
+    fn test_day14_malformed_input() {
+        let mut puzzle = Day14::with_size(11, 7);
+
+        for input in ["", "\n", "p=0,4 v=3,-3\np=6,3 v=-1"] {
+            puzzle.execute_part_1(input);
+            puzzle.execute_part_2(input);
+
+            assert_eq!(puzzle.get_result_part_1(), None);
+            assert_eq!(puzzle.get_result_part_2(), None);
+        }
+    }
+
+    #[test]
+    fn test_day14_render() {
+        let puzzle = Day14::with_size(11, 7);
+        let input = read_test_input(puzzle.day(), None, None);
+
+        let frame = puzzle.render(&input, 100).unwrap();
+
+        // The robots after 100 seconds, as shown in the puzzle description
+        let expected = [
+            "......#..#.",
+            "...........",
+            "#..........",
+            ".##........",
+            ".....#.....",
+            "...##......",
+            ".#....#....",
+        ];
+        assert_eq!(frame, expected.join("\n") + "\n");
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::adventofcode::Puzzle;
+
+#[derive(Default)]
+pub struct Day15 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day15 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    N,
+    S,
+    W,
+    E,
+}
+
+impl Direction {
+    fn coordinates(&self) -> (i32, i32) {
+        match self {
+            Direction::N => (0, -1),
+            Direction::S => (0, 1),
+            Direction::W => (-1, 0),
+            Direction::E => (1, 0),
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::N),
+            'v' => Some(Direction::S),
+            '<' => Some(Direction::W),
+            '>' => Some(Direction::E),
+            _ => None,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Direction::N => '^',
+            Direction::S => 'v',
+            Direction::W => '<',
+            Direction::E => '>',
+        }
+    }
+}
+
+struct Warehouse {
+    grid: Vec<Vec<char>>,
+    robot: (i32, i32),
+}
+
+impl Warehouse {
+    fn get(&self, (x, y): (i32, i32)) -> char {
+        // Anything outside the map behaves like a wall
+        self.grid
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+            .unwrap_or('#')
+    }
+
+    /// Moves the robot one step, pushing every box in the way.
+    ///
+    /// All tiles that have to move are collected first, starting with the robot.
+    /// A wide box pushed up or down also drags its other half along, which may
+    /// in turn push more boxes. If any of them would hit a wall nothing moves.
+    fn move_robot(&mut self, direction: Direction) {
+        let (dx, dy) = direction.coordinates();
+        let vertical = dy != 0;
+
+        let mut to_move = vec![self.robot];
+        let mut seen = HashSet::from([self.robot]);
+        let mut queue = VecDeque::from([self.robot]);
+
+        while let Some((x, y)) = queue.pop_front() {
+            let next = (x + dx, y + dy);
+            let mut pushed = match self.get(next) {
+                '#' => return,
+                'O' => vec![next],
+                '[' if vertical => vec![next, (next.0 + 1, next.1)],
+                ']' if vertical => vec![next, (next.0 - 1, next.1)],
+                '[' | ']' => vec![next],
+                _ => vec![],
+            };
+
+            pushed.retain(|position| seen.insert(*position));
+            to_move.extend(&pushed);
+            queue.extend(pushed);
+        }
+
+        // Move the tiles furthest along the direction first, so nothing gets overwritten
+        to_move.sort_by_key(|&(x, y)| -(x * dx + y * dy));
+        for (x, y) in to_move {
+            let tile = self.grid[y as usize][x as usize];
+            self.grid[(y + dy) as usize][(x + dx) as usize] = tile;
+            self.grid[y as usize][x as usize] = '.';
+        }
+        self.robot = (self.robot.0 + dx, self.robot.1 + dy);
+    }
+
+    /// Sums the GPS coordinates of all boxes, measured to their left edge.
+    fn gps_sum(&self) -> usize {
+        self.grid
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &tile)| tile == 'O' || tile == '[')
+                    .map(move |(x, _)| 100 * y + x)
+            })
+            .sum()
+    }
+
+    fn render(&self) -> String {
+        self.grid
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+/// Returns the warehouse and the robot's moves, or `None` if the input is malformed.
+///
+/// The map has to be rectangular, contain exactly one robot and only walls,
+/// boxes and empty floor. With `wide` every tile is doubled in width.
+fn parse_input(input: &str, wide: bool) -> Option<(Warehouse, Vec<Direction>)> {
+    let mut lines = input.lines();
+
+    let rows: Vec<&str> = lines.by_ref().take_while(|line| !line.is_empty()).collect();
+    let width = rows.first()?.len();
+    if rows.iter().any(|row| row.len() != width)
+        || rows.concat().chars().any(|c| !"#.O@".contains(c))
+        || rows.concat().matches('@').count() != 1
+    {
+        return None;
+    }
+
+    let grid: Vec<Vec<char>> = rows
+        .iter()
+        .map(|line| {
+            if wide {
+                line.chars()
+                    .flat_map(|c| match c {
+                        'O' => ['[', ']'],
+                        '@' => ['@', '.'],
+                        c => [c, c],
+                    })
+                    .collect()
+            } else {
+                line.chars().collect()
+            }
+        })
+        .collect();
+
+    let robot = grid.iter().enumerate().find_map(|(y, row)| {
+        row.iter()
+            .position(|&tile| tile == '@')
+            .map(|x| (x as i32, y as i32))
+    })?;
+
+    let moves = lines
+        .flat_map(|line| line.chars())
+        .map(Direction::from_char)
+        .collect::<Option<Vec<_>>>()?;
+
+    Some((Warehouse { grid, robot }, moves))
+}
+
+/// Moves the robot and returns the sum of the GPS coordinates of the boxes, or
+/// `None` if the input is malformed. With `wide` the warehouse is twice as wide.
+fn simulate(input: &str, wide: bool) -> Option<usize> {
+    let (mut warehouse, moves) = parse_input(input, wide)?;
+
+    for direction in moves {
+        warehouse.move_robot(direction);
+    }
+
+    Some(warehouse.gps_sum())
+}
+
+/// Like the puzzle answers, but also calls `on_frame` with the warehouse
+/// drawn before the robot starts and after each of its moves, e.g.
+/// `Move <:` followed by the map.
+pub fn simulate_with(input: &str, wide: bool, mut on_frame: impl FnMut(String)) -> Option<usize> {
+    let (mut warehouse, moves) = parse_input(input, wide)?;
+
+    on_frame(format!("Initial state:\n{}", warehouse.render()));
+    for direction in moves {
+        warehouse.move_robot(direction);
+        on_frame(format!(
+            "Move {}:\n{}",
+            direction.symbol(),
+            warehouse.render()
+        ));
+    }
+
+    Some(warehouse.gps_sum())
+}
+
+impl Puzzle for Day15 {
+    fn execute_part_1(&mut self, input: &str) {
+        self.part_1_result = simulate(input, false).map(|x| x.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        self.part_2_result = simulate(input, true).map(|x| x.to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        15
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filehelper::read_test_input;
+
+    use super::*;
+
+    #[test]
+    fn test_day15_frames() {
+        let input = read_test_input(15, None, Some(1));
+        let mut frames = Vec::new();
+
+        let result = simulate_with(&input, false, |frame| frames.push(frame));
+
+        assert_eq!(result, Some(2028));
+        assert_eq!(frames.len(), 16);
+        // The first move is blocked by the wall, the second one moves up
+        let expected = [
+            "Move ^:", "########", "#.@O.O.#", "##..O..#", "#...O..#", "#.#.O..#", "#...O..#",
+            "#......#", "########",
+        ];
+        assert_eq!(frames[2], expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn test_day15_wide_boxes_final_state() {
+        let input = read_test_input(15, None, Some(3));
+        let (mut warehouse, moves) = parse_input(&input, true).unwrap();
+
+        for direction in moves {
+            warehouse.move_robot(direction);
+        }
+
+        // The final state shown in the puzzle description
+        let expected = [
+            "##############",
+            "##...[].##..##",
+            "##...@.[]...##",
+            "##....[]....##",
+            "##..........##",
+            "##..........##",
+            "##############",
+        ];
+        assert_eq!(warehouse.render(), expected.join("\n") + "\n");
+    }
+}
@@ -0,0 +1,188 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::adventofcode::Puzzle;
+use crate::grid::parse_maze;
+
+const STEP_COST: usize = 1;
+const TURN_COST: usize = 1000;
+
+#[derive(Default)]
+pub struct Day16 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day16 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+struct Maze {
+    tiles: Vec<char>,
+    width: i32,
+    height: i32,
+    start: usize,
+    end: usize,
+}
+
+impl Maze {
+    /// Returns the index of the tile in the given direction, or `None` if it is a wall.
+    fn step(&self, index: usize, direction: usize) -> Option<usize> {
+        let (dx, dy) = DIRECTIONS[direction];
+        let x = index as i32 % self.width + dx;
+        let y = index as i32 / self.width + dy;
+
+        if x < 0 || x >= self.width || y < 0 || y >= self.height {
+            return None;
+        }
+        let next = (x + y * self.width) as usize;
+        (self.tiles[next] != '#').then_some(next)
+    }
+}
+
+fn parse_input(input: &str) -> Option<Maze> {
+    let (grid, start, end) = parse_maze(input)?;
+    Some(Maze {
+        tiles: grid.tiles,
+        width: grid.width,
+        height: grid.height,
+        start,
+        end,
+    })
+}
+
+/// The result of a Dijkstra search over the states `tile * 4 + direction`.
+struct Search {
+    scores: Vec<usize>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+/// Finds the lowest score for every state, starting on the start tile facing east.
+///
+/// Besides the score, every state remembers all states it can be reached from
+/// with that same lowest score, so all best paths can be traced back later.
+fn search(maze: &Maze) -> Search {
+    let state_count = maze.tiles.len() * 4;
+    let mut scores = vec![usize::MAX; state_count];
+    let mut predecessors = vec![Vec::new(); state_count];
+    let mut queue = BinaryHeap::new();
+
+    let start = maze.start * 4;
+    scores[start] = 0;
+    queue.push(Reverse((0, start)));
+
+    while let Some(Reverse((score, state))) = queue.pop() {
+        if score > scores[state] {
+            continue;
+        }
+
+        let (index, direction) = (state / 4, state % 4);
+        let forward = maze
+            .step(index, direction)
+            .map(|next| (next * 4 + direction, score + STEP_COST));
+        let turns = [(direction + 1) % 4, (direction + 3) % 4]
+            .map(|turned| (index * 4 + turned, score + TURN_COST));
+
+        for (next, next_score) in forward.into_iter().chain(turns) {
+            if next_score < scores[next] {
+                scores[next] = next_score;
+                predecessors[next] = vec![state];
+                queue.push(Reverse((next_score, next)));
+            } else if next_score == scores[next] {
+                predecessors[next].push(state);
+            }
+        }
+    }
+
+    Search {
+        scores,
+        predecessors,
+    }
+}
+
+/// Returns the end states reached with the lowest score, or `None` if the end is unreachable.
+fn best_end_states(maze: &Maze, search: &Search) -> Option<(usize, Vec<usize>)> {
+    let end_states = maze.end * 4..maze.end * 4 + 4;
+    let best = end_states
+        .clone()
+        .map(|state| search.scores[state])
+        .min()
+        .filter(|&score| score != usize::MAX)?;
+
+    let states = end_states
+        .filter(|&state| search.scores[state] == best)
+        .collect();
+    Some((best, states))
+}
+
+/// Counts the tiles on any best path by walking the predecessors back from the end.
+fn tiles_on_best_paths(search: &Search, end_states: Vec<usize>) -> usize {
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut stack = end_states;
+
+    while let Some(state) = stack.pop() {
+        if visited.insert(state) {
+            stack.extend(&search.predecessors[state]);
+        }
+    }
+
+    visited
+        .iter()
+        .map(|state| state / 4)
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+impl Puzzle for Day16 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(maze) = parse_input(input) else {
+            return;
+        };
+
+        let search = search(&maze);
+        self.part_1_result = best_end_states(&maze, &search).map(|(score, _)| score.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(maze) = parse_input(input) else {
+            return;
+        };
+
+        let search = search(&maze);
+        self.part_2_result = best_end_states(&maze, &search)
+            .map(|(_, states)| tiles_on_best_paths(&search, states).to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day16_unreachable_end() {
+        let mut puzzle = Day16::new();
+
+        puzzle.execute_part_1("#####\n#S#E#\n#####");
+
+        assert_eq!(puzzle.get_result_part_1(), None);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::adventofcode::Puzzle;
+
+/// Upper bound on executed instructions, so programs that never halt are rejected.
+const MAX_STEPS: usize = 100_000;
+
+#[derive(Default)]
+pub struct Day17 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day17 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+/// The 3-bit computer with its three registers and output buffer.
+#[derive(Debug, Clone, PartialEq)]
+struct Computer {
+    a: u64,
+    b: u64,
+    c: u64,
+    instruction_pointer: usize,
+    output: Vec<u8>,
+}
+
+impl Computer {
+    fn new(a: u64, b: u64, c: u64) -> Self {
+        Self {
+            a,
+            b,
+            c,
+            instruction_pointer: 0,
+            output: Vec::new(),
+        }
+    }
+
+    /// Returns the value of a combo operand, or `None` for the reserved operand 7.
+    fn combo(&self, operand: u8) -> Option<u64> {
+        match operand {
+            0..=3 => Some(operand as u64),
+            4 => Some(self.a),
+            5 => Some(self.b),
+            6 => Some(self.c),
+            _ => None,
+        }
+    }
+
+    /// Divides register A by two to the power of the combo operand.
+    fn divide(&self, operand: u8) -> Option<u64> {
+        let shift = self.combo(operand)?;
+        Some(if shift >= 64 { 0 } else { self.a >> shift })
+    }
+
+    /// Executes a single instruction. Returns `Some(false)` once the program has halted.
+    fn step(&mut self, program: &[u8]) -> Option<bool> {
+        let (Some(&opcode), Some(&operand)) = (
+            program.get(self.instruction_pointer),
+            program.get(self.instruction_pointer + 1),
+        ) else {
+            return Some(false);
+        };
+        self.instruction_pointer += 2;
+
+        match opcode {
+            0 => self.a = self.divide(operand)?,
+            1 => self.b ^= operand as u64,
+            2 => self.b = self.combo(operand)? % 8,
+            3 => {
+                if self.a != 0 {
+                    self.instruction_pointer = operand as usize;
+                }
+            }
+            4 => self.b ^= self.c,
+            5 => self.output.push((self.combo(operand)? % 8) as u8),
+            6 => self.b = self.divide(operand)?,
+            7 => self.c = self.divide(operand)?,
+            _ => return None,
+        }
+        Some(true)
+    }
+
+    /// Runs the program until it halts and returns its output, or `None` if it
+    /// uses an invalid operand or does not halt within `MAX_STEPS`.
+    fn run(&mut self, program: &[u8]) -> Option<Vec<u8>> {
+        for _ in 0..MAX_STEPS {
+            if !self.step(program)? {
+                return Some(self.output.clone());
+            }
+        }
+        None
+    }
+}
+
+/// Returns the computer and the program, or `None` if the input is malformed.
+fn parse_input(input: &str) -> Option<(Computer, Vec<u8>)> {
+    let mut lines = input.lines();
+    let mut register = |name: &str| -> Option<u64> {
+        lines
+            .next()?
+            .strip_prefix(&format!("Register {}: ", name))?
+            .parse()
+            .ok()
+    };
+    let computer = Computer::new(register("A")?, register("B")?, register("C")?);
+
+    let program = lines
+        .find(|line| !line.is_empty())?
+        .strip_prefix("Program: ")?
+        .split(',')
+        .map(|value| value.parse().ok().filter(|&value| value < 8))
+        .collect::<Option<Vec<u8>>>()?;
+
+    Some((computer, program))
+}
+
+/// Pretty-prints the program in the puzzle input, one instruction per line.
+///
+/// Every line shows the address, the mnemonic with its operand and what the
+/// instruction does. Returns `None` if the input cannot be parsed.
+pub fn disassemble(input: &str) -> Option<String> {
+    let (_, program) = parse_input(input)?;
+
+    let lines: Vec<String> = program
+        .chunks(2)
+        .enumerate()
+        .map(|(i, instruction)| {
+            let opcode = instruction[0];
+            let Some(&operand) = instruction.get(1) else {
+                return format!("{:>3}: {}", i * 2, MNEMONICS[opcode as usize]);
+            };
+
+            let combo = match operand {
+                0..=3 => operand.to_string(),
+                4 => "A".to_string(),
+                5 => "B".to_string(),
+                6 => "C".to_string(),
+                _ => "?".to_string(),
+            };
+            let (shown, effect) = match opcode {
+                0 => (combo.clone(), format!("A = A >> {}", combo)),
+                1 => (operand.to_string(), format!("B = B ^ {}", operand)),
+                2 => (combo.clone(), format!("B = {} % 8", combo)),
+                3 => (
+                    operand.to_string(),
+                    format!("jump to {} if A != 0", operand),
+                ),
+                4 => (operand.to_string(), "B = B ^ C".to_string()),
+                5 => (combo.clone(), format!("output {} % 8", combo)),
+                6 => (combo.clone(), format!("B = A >> {}", combo)),
+                _ => (combo.clone(), format!("C = A >> {}", combo)),
+            };
+
+            format!(
+                "{:>3}: {} {:<4} {}",
+                i * 2,
+                MNEMONICS[opcode as usize],
+                shown,
+                effect
+            )
+        })
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+/// Finds the smallest value for register A that makes the program output itself.
+///
+/// The programs consume register A three bits per output value, so the last
+/// output only depends on the highest octal digit of A. The digits are
+/// therefore found from the highest one down: every candidate that reproduces
+/// the tail of the program is extended by one more digit. Trying digits in
+/// increasing order makes the first complete match the smallest.
+fn find_quine(computer: &Computer, program: &[u8], a: u64, matched: usize) -> Option<u64> {
+    if matched == program.len() {
+        return Some(a);
+    }
+
+    let tail = &program[program.len() - matched - 1..];
+    (0..8).find_map(|digit| {
+        let candidate = a.checked_mul(8)? + digit;
+        if candidate == 0 {
+            return None;
+        }
+
+        // Every candidate starts from the initial registers, not the ones left by a previous run
+        let mut run = Computer::new(candidate, computer.b, computer.c);
+        if run.run(program)? != tail {
+            return None;
+        }
+        find_quine(computer, program, candidate, matched + 1)
+    })
+}
+
+impl Puzzle for Day17 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some((mut computer, program)) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = computer.run(&program).map(|output| {
+            output
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some((computer, program)) = parse_input(input) else {
+            return;
+        };
+
+        self.part_2_result = find_quine(&computer, &program, 0, 0).map(|a| a.to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        17
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filehelper::read_test_input;
+
+    use super::*;
+
+    #[test]
+    fn test_day17_instructions() {
+        let mut computer = Computer::new(0, 0, 9);
+        computer.run(&[2, 6]);
+        assert_eq!(computer.b, 1);
+
+        let mut computer = Computer::new(10, 0, 0);
+        assert_eq!(computer.run(&[5, 0, 5, 1, 5, 4]), Some(vec![0, 1, 2]));
+
+        let mut computer = Computer::new(2024, 0, 0);
+        let output = computer.run(&[0, 1, 5, 4, 3, 0]);
+        assert_eq!(output, Some(vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]));
+        assert_eq!(computer.a, 0);
+
+        let mut computer = Computer::new(0, 29, 0);
+        computer.run(&[1, 7]);
+        assert_eq!(computer.b, 26);
+
+        let mut computer = Computer::new(0, 2024, 43690);
+        computer.run(&[4, 0]);
+        assert_eq!(computer.b, 44354);
+    }
+
+    #[test]
+    fn test_day17_quine_real_style_program() {
+        // B = A % 8 ^ 3, C = A >> B, A >>= 3, B = B ^ 5 ^ C, output B
+        let program = [2, 4, 1, 3, 7, 5, 0, 3, 1, 5, 4, 4, 5, 5, 3, 0];
+
+        let a = find_quine(&Computer::new(0, 0, 0), &program, 0, 0).unwrap();
+
+        let mut computer = Computer::new(a, 0, 0);
+        assert_eq!(computer.run(&program), Some(program.to_vec()));
+    }
+
+    #[test]
+    fn test_day17_quine_reads_initial_registers() {
+        // B = A % 8 ^ C, A >>= 3, output B, repeat while A != 0, then C = A.
+        // Every output depends on the initial C, which is overwritten at the end.
+        let program = [2, 4, 4, 0, 0, 3, 5, 5, 3, 0, 7, 0];
+
+        let a = find_quine(&Computer::new(0, 7, 4), &program, 0, 0).unwrap();
+
+        let mut computer = Computer::new(a, 7, 4);
+        assert_eq!(computer.run(&program), Some(program.to_vec()));
+    }
+
+    #[test]
+    fn test_day17_endless_program() {
+        let mut computer = Computer::new(1, 0, 0);
+        assert_eq!(computer.run(&[3, 0]), None);
+    }
+
+    #[test]
+    fn test_day17_disassemble() {
+        let input = read_test_input(17, None, Some(2));
+
+        let expected = [
+            "  0: adv 3    A = A >> 3",
+            "  2: out A    output A % 8",
+            "  4: jnz 0    jump to 0 if A != 0",
+        ];
+        assert_eq!(disassemble(&input), Some(expected.join("\n")));
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use crate::adventofcode::Puzzle;
+
+pub struct Day18 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+    size: i32,
+    bytes: usize,
+}
+
+impl Default for Day18 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Day18 {
+    pub fn new() -> Self {
+        Self::with_size(71, 1024)
+    }
+
+    /// Creates the puzzle for a memory space of `size` by `size` cells where part 1
+    /// looks at the first `bytes` bytes, e.g. 7 and 12 for the example.
+    /// The size must be positive.
+    pub fn with_size(size: i32, bytes: usize) -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+            size,
+            bytes,
+        }
+    }
+}
+
+type Position = (i32, i32);
+
+/// Returns the positions of the falling bytes, or `None` if any of them is malformed
+/// or lies outside a memory space of the given size.
+fn parse_input(input: &str, size: i32) -> Option<Vec<Position>> {
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = line.split_once(',')?;
+            let (x, y) = (x.parse().ok()?, y.parse().ok()?);
+            ((0..size).contains(&x) && (0..size).contains(&y)).then_some((x, y))
+        })
+        .collect()
+}
+
+/// The memory space, remembering for every cell which byte corrupts it first.
+struct Memory {
+    size: i32,
+    corrupted_at: Vec<usize>,
+}
+
+impl Memory {
+    fn new(bytes: &[Position], size: i32) -> Self {
+        let mut corrupted_at = vec![usize::MAX; (size * size) as usize];
+        for (i, &(x, y)) in bytes.iter().enumerate().rev() {
+            corrupted_at[(x + y * size) as usize] = i;
+        }
+        Self { size, corrupted_at }
+    }
+
+    /// Returns the number of steps from the top left to the bottom right corner
+    /// once the first `fallen` bytes have landed, or `None` if the exit is cut off.
+    fn shortest_path(&self, fallen: usize) -> Option<usize> {
+        let is_free = |index: usize| self.corrupted_at[index] >= fallen;
+        let exit = self.corrupted_at.len() - 1;
+        if !is_free(0) {
+            return None;
+        }
+
+        let mut steps = vec![usize::MAX; self.corrupted_at.len()];
+        let mut queue = VecDeque::from([0]);
+        steps[0] = 0;
+
+        while let Some(index) = queue.pop_front() {
+            if index == exit {
+                return Some(steps[index]);
+            }
+
+            let (x, y) = (index as i32 % self.size, index as i32 / self.size);
+            for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if nx < 0 || nx >= self.size || ny < 0 || ny >= self.size {
+                    continue;
+                }
+                let next = (nx + ny * self.size) as usize;
+                if is_free(next) && steps[next] == usize::MAX {
+                    steps[next] = steps[index] + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Puzzle for Day18 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(bytes) = parse_input(input, self.size) else {
+            return;
+        };
+
+        let memory = Memory::new(&bytes, self.size);
+        self.part_1_result = memory
+            .shortest_path(self.bytes)
+            .map(|steps| steps.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(bytes) = parse_input(input, self.size) else {
+            return;
+        };
+
+        // Once the exit is cut off it stays cut off, so binary search for the
+        // smallest number of fallen bytes that blocks it.
+        let memory = Memory::new(&bytes, self.size);
+        let (mut reachable, mut blocked) = (0, bytes.len() + 1);
+        while blocked - reachable > 1 {
+            let fallen = (reachable + blocked) / 2;
+            if memory.shortest_path(fallen).is_some() {
+                reachable = fallen;
+            } else {
+                blocked = fallen;
+            }
+        }
+
+        if let Some((x, y)) = bytes.get(blocked - 1) {
+            self.part_2_result = Some(format!("{},{}", x, y));
+        }
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        18
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day18_part_2_never_blocked() {
+        let mut puzzle = Day18::with_size(7, 12);
+
+        puzzle.execute_part_2("1,1\n2,2");
+
+        assert_eq!(puzzle.get_result_part_2(), None);
+    }
+}
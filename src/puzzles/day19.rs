@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use crate::adventofcode::Puzzle;
+
+#[derive(Default)]
+pub struct Day19 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day19 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    children: HashMap<u8, usize>,
+    is_pattern: bool,
+}
+
+/// A prefix tree over all towel patterns, so every pattern that starts at a given
+/// position of a design is found in a single walk along the design.
+struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut trie = Self {
+            nodes: vec![Node::default()],
+        };
+
+        for pattern in patterns {
+            let mut node = 0;
+            for &stripe in pattern.as_bytes() {
+                node = match trie.nodes[node].children.get(&stripe) {
+                    Some(&child) => child,
+                    None => {
+                        trie.nodes.push(Node::default());
+                        let child = trie.nodes.len() - 1;
+                        trie.nodes[node].children.insert(stripe, child);
+                        child
+                    }
+                };
+            }
+            trie.nodes[node].is_pattern = true;
+        }
+
+        trie
+    }
+
+    /// Returns the lengths of all patterns the given stripes start with.
+    fn prefix_lengths<'a>(&'a self, stripes: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        stripes
+            .iter()
+            .scan(0, |node, stripe| {
+                *node = *self.nodes[*node].children.get(stripe)?;
+                Some(*node)
+            })
+            .enumerate()
+            .filter(|&(_, node)| self.nodes[node].is_pattern)
+            .map(|(i, _)| i + 1)
+    }
+
+    /// Counts the ways the design can be arranged from the patterns, or `None` if
+    /// the count does not fit in a `u64`.
+    ///
+    /// Works backwards from the end of the design, so the number of arrangements
+    /// for every suffix is known by the time a pattern reaches it.
+    fn arrangements(&self, design: &str) -> Option<u64> {
+        let stripes = design.as_bytes();
+        let mut ways = vec![0u64; stripes.len() + 1];
+        ways[stripes.len()] = 1;
+
+        for start in (0..stripes.len()).rev() {
+            for length in self.prefix_lengths(&stripes[start..]) {
+                ways[start] = ways[start].checked_add(ways[start + length])?;
+            }
+        }
+
+        Some(ways[0])
+    }
+}
+
+/// Returns the available patterns and the wanted designs, or `None` if the input is malformed.
+fn parse_input(input: &str) -> Option<(Vec<&str>, Vec<&str>)> {
+    let mut lines = input.lines();
+
+    let patterns: Vec<&str> = lines.next()?.split(", ").collect();
+    if patterns.iter().any(|pattern| pattern.is_empty()) || !lines.next()?.is_empty() {
+        return None;
+    }
+
+    Some((patterns, lines.collect()))
+}
+
+impl Puzzle for Day19 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some((patterns, designs)) = parse_input(input) else {
+            return;
+        };
+
+        // A count too large for a u64 still means the design is possible
+        let trie = Trie::new(patterns);
+        let result = designs
+            .iter()
+            .filter(|design| trie.arrangements(design) != Some(0))
+            .count();
+
+        self.part_1_result = Some(result.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some((patterns, designs)) = parse_input(input) else {
+            return;
+        };
+
+        let trie = Trie::new(patterns);
+        let result = designs.iter().try_fold(0u64, |total, design| {
+            total.checked_add(trie.arrangements(design)?)
+        });
+
+        self.part_2_result = result.map(|x| x.to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        19
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day19_long_design() {
+        // With patterns of length 1 and 2 the arrangements are Fibonacci numbers
+        let trie = Trie::new(["w", "ww"]);
+
+        assert_eq!(
+            trie.arrangements(&"w".repeat(90)),
+            Some(4660046610375530309)
+        );
+        assert_eq!(trie.arrangements(&"w".repeat(100)), None);
+    }
+}
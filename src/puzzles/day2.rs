@@ -1,5 +1,6 @@
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day2 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -19,7 +20,7 @@ fn check_report(row: &[i32]) -> bool {
         .windows(2)
         .filter(|window| {
             if let [a, b] = *window {
-                b > a && (b - a).abs() <= 3
+                b > a && b.abs_diff(*a) <= 3
             } else {
                 false
             }
@@ -29,28 +30,34 @@ fn check_report(row: &[i32]) -> bool {
         .windows(2)
         .filter(|window| {
             if let [a, b] = *window {
-                b < a && (b - a).abs() <= 3
+                b < a && b.abs_diff(*a) <= 3
             } else {
                 false
             }
         })
         .count();
 
-    let expected = row.len() - 1;
+    let expected = row.len().saturating_sub(1);
 
     increases == expected || decreases == expected
 }
 
+fn parse_input(input: &str) -> Option<Vec<Vec<i32>>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|num| num.parse().ok())
+                .collect()
+        })
+        .collect()
+}
+
 impl Puzzle for Day2 {
     fn execute_part_1(&mut self, input: &str) {
-        let numbers: Vec<Vec<i32>> = input
-            .lines()
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|num| num.parse().unwrap())
-                    .collect()
-            })
-            .collect();
+        let Some(numbers) = parse_input(input) else {
+            return;
+        };
 
         let safe_reports = numbers.iter().filter(|row| check_report(row)).count();
 
@@ -58,14 +65,9 @@ impl Puzzle for Day2 {
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let numbers: Vec<Vec<i32>> = input
-            .lines()
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|num| num.parse().unwrap())
-                    .collect()
-            })
-            .collect();
+        let Some(numbers) = parse_input(input) else {
+            return;
+        };
 
         let mut safe_reports = 0;
         numbers.iter().for_each(|row| {
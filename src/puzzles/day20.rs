@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+
+use crate::adventofcode::Puzzle;
+use crate::grid::parse_maze;
+
+pub struct Day20 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+    threshold: usize,
+}
+
+impl Default for Day20 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Day20 {
+    pub fn new() -> Self {
+        Self::with_threshold(100)
+    }
+
+    /// Creates the puzzle counting the cheats that save at least `threshold`
+    /// picoseconds, e.g. a small number for the example.
+    pub fn with_threshold(threshold: usize) -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+            threshold,
+        }
+    }
+}
+
+struct RaceTrack {
+    tiles: Vec<char>,
+    width: i32,
+    height: i32,
+    start: usize,
+    end: usize,
+}
+
+impl RaceTrack {
+    /// Returns the index of the tile at the given offset, or `None` if it is outside the map.
+    fn neighbour(&self, index: usize, (dx, dy): (i32, i32)) -> Option<usize> {
+        let x = index as i32 % self.width + dx;
+        let y = index as i32 / self.width + dy;
+
+        if x >= 0 && x < self.width && y >= 0 && y < self.height {
+            Some((x + y * self.width) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of steps from the given tile to every other tile,
+    /// or `usize::MAX` where a tile cannot be reached without cheating.
+    fn distances_from(&self, from: usize) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.tiles.len()];
+        let mut queue = VecDeque::from([from]);
+        distances[from] = 0;
+
+        while let Some(index) = queue.pop_front() {
+            for offset in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                if let Some(next) = self.neighbour(index, offset) {
+                    if self.tiles[next] != '#' && distances[next] == usize::MAX {
+                        distances[next] = distances[index] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Counts the cheats of at most `max_length` picoseconds that save at least
+    /// `threshold` picoseconds, or `None` if the end cannot be reached at all.
+    ///
+    /// A cheat jumps from a tile reachable from the start to any tile within
+    /// `max_length` steps from which the end can be reached. Its race time is
+    /// the distance to where it starts, its length and the distance from where
+    /// it ends, so both distance maps only have to be computed once.
+    fn count_cheats(&self, max_length: i32, threshold: usize) -> Option<usize> {
+        let from_start = self.distances_from(self.start);
+        let to_end = self.distances_from(self.end);
+        let fastest = from_start[self.end];
+        if fastest == usize::MAX {
+            return None;
+        }
+
+        let offsets: Vec<(i32, i32)> = (-max_length..=max_length)
+            .flat_map(|dy| {
+                let remaining = max_length - dy.abs();
+                (-remaining..=remaining).map(move |dx| (dx, dy))
+            })
+            .collect();
+
+        let count = (0..self.tiles.len())
+            .filter(|&index| from_start[index] != usize::MAX)
+            .map(|index| {
+                offsets
+                    .iter()
+                    .filter_map(|&offset| {
+                        let target = self.neighbour(index, offset)?;
+                        let length = (offset.0.abs() + offset.1.abs()) as usize;
+                        let time = from_start[index]
+                            .checked_add(length)?
+                            .checked_add(to_end[target])?;
+                        let saved = fastest.checked_sub(time)?;
+                        (saved >= threshold).then_some(())
+                    })
+                    .count()
+            })
+            .sum();
+
+        Some(count)
+    }
+}
+
+fn parse_input(input: &str) -> Option<RaceTrack> {
+    let (grid, start, end) = parse_maze(input)?;
+    Some(RaceTrack {
+        tiles: grid.tiles,
+        width: grid.width,
+        height: grid.height,
+        start,
+        end,
+    })
+}
+
+impl Puzzle for Day20 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(track) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = track.count_cheats(2, self.threshold).map(|x| x.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(track) = parse_input(input) else {
+            return;
+        };
+
+        self.part_2_result = track
+            .count_cheats(20, self.threshold)
+            .map(|x| x.to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        20
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filehelper::read_test_input;
+
+    use super::*;
+
+    #[test]
+    fn test_day20_thresholds() {
+        let input = read_test_input(20, None, None);
+        let track = parse_input(&input).unwrap();
+
+        assert_eq!(track.count_cheats(2, 20), Some(5));
+        assert_eq!(track.count_cheats(2, 64), Some(1));
+        assert_eq!(track.count_cheats(2, 65), Some(0));
+        assert_eq!(track.count_cheats(20, 72), Some(29));
+        assert_eq!(track.count_cheats(20, 76), Some(3));
+        assert_eq!(track.count_cheats(20, usize::MAX), Some(0));
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use crate::adventofcode::Puzzle;
+
+const NUMERIC_KEYPAD: [&str; 4] = ["789", "456", "123", " 0A"];
+const DIRECTIONAL_KEYPAD: [&str; 2] = [" ^A", "<v>"];
+
+pub struct Day21 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+    part_1_robots: usize,
+    part_2_robots: usize,
+}
+
+impl Default for Day21 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Day21 {
+    pub fn new() -> Self {
+        Self::with_robots(2, 25)
+    }
+
+    /// Creates the puzzle with a different number of robots using directional
+    /// keypads between you and the robot at the numeric keypad, for each part.
+    pub fn with_robots(part_1_robots: usize, part_2_robots: usize) -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+            part_1_robots,
+            part_2_robots,
+        }
+    }
+}
+
+struct Keypad {
+    keys: HashMap<char, (i32, i32)>,
+    gap: (i32, i32),
+}
+
+impl Keypad {
+    fn new(rows: &[&str]) -> Self {
+        let mut keys = HashMap::new();
+        let mut gap = (0, 0);
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, key) in row.chars().enumerate() {
+                if key == ' ' {
+                    gap = (x as i32, y as i32);
+                } else {
+                    keys.insert(key, (x as i32, y as i32));
+                }
+            }
+        }
+
+        Self { keys, gap }
+    }
+
+    /// Returns the ways to move the arm from one key to another and press it.
+    ///
+    /// Only the two paths that do all horizontal moves first or all vertical
+    /// moves first are considered. Zigzagging never helps, since the robot
+    /// controlling this one would have to travel more. Paths over the gap
+    /// are left out, because the robot would panic there.
+    fn paths(&self, from: char, to: char) -> Vec<String> {
+        let (x1, y1) = self.keys[&from];
+        let (x2, y2) = self.keys[&to];
+
+        let horizontal = if x2 > x1 { ">" } else { "<" }.repeat(x1.abs_diff(x2) as usize);
+        let vertical = if y2 > y1 { "v" } else { "^" }.repeat(y1.abs_diff(y2) as usize);
+
+        let mut paths = Vec::new();
+        if (x2, y1) != self.gap {
+            paths.push(format!("{}{}A", horizontal, vertical));
+        }
+        if (x1, y2) != self.gap {
+            paths.push(format!("{}{}A", vertical, horizontal));
+        }
+        paths.dedup();
+        paths
+    }
+}
+
+/// Computes the number of button presses you need, remembering the cost of every
+/// move on a directional keypad per (from, to, depth).
+struct Solver {
+    numeric: Keypad,
+    directional: Keypad,
+    memo: HashMap<(char, char, usize), Option<u64>>,
+}
+
+impl Solver {
+    fn new() -> Self {
+        Self {
+            numeric: Keypad::new(&NUMERIC_KEYPAD),
+            directional: Keypad::new(&DIRECTIONAL_KEYPAD),
+            memo: HashMap::new(),
+        }
+    }
+
+    /// Returns the presses needed for the given directional keys to be pressed
+    /// with `depth` robots in between, starting with every arm on `A`, or
+    /// `None` if the count overflows.
+    fn sequence_cost(&mut self, keys: &str, depth: usize) -> Option<u64> {
+        if depth == 0 {
+            return Some(keys.len() as u64);
+        }
+
+        let mut previous = 'A';
+        keys.chars().try_fold(0u64, |sum, key| {
+            let cost = self.move_cost(previous, key, depth)?;
+            previous = key;
+            sum.checked_add(cost)
+        })
+    }
+
+    /// Returns the presses needed to move a robot's arm between two directional
+    /// keys and press the second one, with `depth` robots controlling it, or
+    /// `None` if the count overflows.
+    fn move_cost(&mut self, from: char, to: char, depth: usize) -> Option<u64> {
+        if let Some(&cost) = self.memo.get(&(from, to, depth)) {
+            return cost;
+        }
+
+        let cost = self.cheapest_path(&self.directional.paths(from, to), depth - 1);
+
+        self.memo.insert((from, to, depth), cost);
+        cost
+    }
+
+    /// Returns the cost of the cheapest of the paths, or `None` if every one of
+    /// them overflows.
+    fn cheapest_path(&mut self, paths: &[String], depth: usize) -> Option<u64> {
+        paths
+            .iter()
+            .filter_map(|path| self.sequence_cost(path, depth))
+            .min()
+    }
+
+    /// Returns the presses needed to type the code on the numeric keypad, or
+    /// `None` if the count overflows.
+    fn code_cost(&mut self, code: &str, robots: usize) -> Option<u64> {
+        let mut previous = 'A';
+        code.chars().try_fold(0u64, |sum, key| {
+            let paths = self.numeric.paths(previous, key);
+            previous = key;
+            sum.checked_add(self.cheapest_path(&paths, robots)?)
+        })
+    }
+}
+
+/// Returns the codes with their numeric part, or `None` if a code contains anything
+/// else than numeric keypad keys or its numeric part cannot be read.
+fn parse_input(input: &str) -> Option<Vec<(&str, u64)>> {
+    input
+        .lines()
+        .map(|code| {
+            if code.is_empty() || !code.chars().all(|c| NUMERIC_KEYPAD.concat().contains(c)) {
+                return None;
+            }
+
+            let digits = code.trim_end_matches('A');
+            let value = if digits.is_empty() {
+                0
+            } else {
+                digits.parse().ok()?
+            };
+            Some((code, value))
+        })
+        .collect()
+}
+
+/// Sums the complexities of all codes, or `None` if the input is malformed or the sum overflows.
+fn complexity_sum(input: &str, robots: usize) -> Option<u64> {
+    let codes = parse_input(input)?;
+    let mut solver = Solver::new();
+
+    codes.iter().try_fold(0u64, |sum, &(code, value)| {
+        sum.checked_add(solver.code_cost(code, robots)?.checked_mul(value)?)
+    })
+}
+
+impl Puzzle for Day21 {
+    fn execute_part_1(&mut self, input: &str) {
+        self.part_1_result = complexity_sum(input, self.part_1_robots).map(|x| x.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        self.part_2_result = complexity_sum(input, self.part_2_robots).map(|x| x.to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        21
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day21_code_lengths() {
+        let mut solver = Solver::new();
+
+        // The shortest sequences from the example, typed by you with two robots in between
+        assert_eq!(solver.code_cost("029A", 2), Some(68));
+        assert_eq!(solver.code_cost("980A", 2), Some(60));
+        assert_eq!(solver.code_cost("179A", 2), Some(68));
+        assert_eq!(solver.code_cost("456A", 2), Some(64));
+        assert_eq!(solver.code_cost("379A", 2), Some(64));
+
+        // Fewer robots in between
+        assert_eq!(
+            solver.code_cost("029A", 0),
+            Some("<A^A>^^AvvvA".len() as u64)
+        );
+        assert_eq!(
+            solver.code_cost("029A", 1),
+            Some("v<<A>>^A<A>AvA<^AA>A<vAAA>^A".len() as u64)
+        );
+    }
+
+    #[test]
+    fn test_day21_cost_overflow() {
+        let mut puzzle = Day21::with_robots(2, 60);
+
+        puzzle.execute_part_2("029A");
+
+        assert_eq!(puzzle.get_result_part_2(), None);
+    }
+
+    #[test]
+    fn test_day21_avoids_gap() {
+        let numeric = Keypad::new(&NUMERIC_KEYPAD);
+        let directional = Keypad::new(&DIRECTIONAL_KEYPAD);
+
+        assert_eq!(numeric.paths('A', '1'), vec!["^<<A".to_string()]);
+        assert_eq!(numeric.paths('7', '0'), vec![">vvvA".to_string()]);
+        assert_eq!(directional.paths('<', '^'), vec![">^A".to_string()]);
+        assert_eq!(directional.paths('A', '<'), vec!["v<<A".to_string()]);
+    }
+}
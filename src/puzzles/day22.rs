@@ -0,0 +1,118 @@
+use crate::adventofcode::Puzzle;
+
+const ITERATIONS: usize = 2000;
+const PRUNE_MASK: u64 = (1 << 24) - 1;
+
+/// Price changes lie within -9..=9, so a sequence of four of them is a number in base 19.
+const SEQUENCE_COUNT: usize = 19 * 19 * 19 * 19;
+
+#[derive(Default)]
+pub struct Day22 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day22 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+/// Evolves a secret number once. Multiplying and dividing by powers of two
+/// are shifts, and pruning keeps the lowest 24 bits.
+fn next_secret(secret: u64) -> u64 {
+    let secret = (secret ^ (secret << 6)) & PRUNE_MASK;
+    let secret = (secret ^ (secret >> 5)) & PRUNE_MASK;
+    (secret ^ (secret << 11)) & PRUNE_MASK
+}
+
+fn parse_input(input: &str) -> Option<Vec<u64>> {
+    input.lines().map(|line| line.parse().ok()).collect()
+}
+
+/// Adds up the bananas every sequence of four changes would buy, using an
+/// array indexed by the encoded sequence. Only the first time a sequence
+/// shows up for a buyer counts, which is tracked per sequence with the
+/// index of the buyer that last saw it.
+fn most_bananas(secrets: &[u64]) -> u64 {
+    let mut bananas = vec![0u64; SEQUENCE_COUNT];
+    let mut last_seen_by = vec![usize::MAX; SEQUENCE_COUNT];
+
+    for (buyer, &secret) in secrets.iter().enumerate() {
+        let mut secret = secret;
+        let mut price = secret % 10;
+        let mut sequence = 0;
+
+        for i in 0..ITERATIONS {
+            secret = next_secret(secret);
+            let next_price = secret % 10;
+            let change = (next_price + 9 - price) as usize;
+            sequence = (sequence * 19 + change) % SEQUENCE_COUNT;
+            price = next_price;
+
+            if i >= 3 && last_seen_by[sequence] != buyer {
+                last_seen_by[sequence] = buyer;
+                bananas[sequence] += price;
+            }
+        }
+    }
+
+    bananas.into_iter().max().unwrap_or(0)
+}
+
+impl Puzzle for Day22 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(secrets) = parse_input(input) else {
+            return;
+        };
+
+        let result: u64 = secrets
+            .iter()
+            .map(|&secret| (0..ITERATIONS).fold(secret, |secret, _| next_secret(secret)))
+            .sum();
+
+        self.part_1_result = Some(result.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(secrets) = parse_input(input) else {
+            return;
+        };
+
+        self.part_2_result = Some(most_bananas(&secrets).to_string());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        22
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day22_next_secret() {
+        let expected = [
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254,
+        ];
+
+        let mut secret = 123;
+        for value in expected {
+            secret = next_secret(secret);
+            assert_eq!(secret, value);
+        }
+    }
+}
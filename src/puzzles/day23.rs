@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::adventofcode::Puzzle;
+
+#[derive(Default)]
+pub struct Day23 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day23 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+/// The network as an undirected graph, with every computer referred to by its index.
+struct Network<'a> {
+    names: Vec<&'a str>,
+    neighbours: Vec<HashSet<usize>>,
+}
+
+/// Reads the connections, or `None` if a line is not two different computers joined by `-`.
+fn parse_input(input: &str) -> Option<Network<'_>> {
+    let mut network = Network {
+        names: Vec::new(),
+        neighbours: Vec::new(),
+    };
+    let mut indexes: HashMap<&str, usize> = HashMap::new();
+
+    for line in input.lines() {
+        let (a, b) = line.split_once('-')?;
+        if a.is_empty() || b.is_empty() || a == b {
+            return None;
+        }
+
+        let [a, b] = [a, b].map(|name| {
+            *indexes.entry(name).or_insert_with(|| {
+                network.names.push(name);
+                network.neighbours.push(HashSet::new());
+                network.names.len() - 1
+            })
+        });
+        network.neighbours[a].insert(b);
+        network.neighbours[b].insert(a);
+    }
+
+    Some(network)
+}
+
+impl Network<'_> {
+    /// Counts the sets of three interconnected computers where at least one name starts with `t`.
+    fn count_t_triangles(&self) -> usize {
+        let mut count = 0;
+
+        for (a, neighbours) in self.neighbours.iter().enumerate() {
+            for &b in neighbours.iter().filter(|&&b| b > a) {
+                count += neighbours
+                    .intersection(&self.neighbours[b])
+                    .filter(|&&c| c > b)
+                    .filter(|&&c| [a, b, c].iter().any(|&i| self.names[i].starts_with('t')))
+                    .count();
+            }
+        }
+
+        count
+    }
+
+    /// Finds the largest clique with the Bron–Kerbosch algorithm.
+    ///
+    /// `clique` is the clique built so far, `candidates` are the computers that
+    /// could still extend it and `excluded` those that were already tried.
+    /// Choosing the pivot with the most candidates as neighbours skips all of
+    /// its neighbours, since any maximal clique found through them could also
+    /// be found through the pivot or one of its non-neighbours.
+    fn largest_clique(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: HashSet<usize>,
+        mut excluded: HashSet<usize>,
+        largest: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            if clique.len() > largest.len() {
+                *largest = clique.clone();
+            }
+            return;
+        }
+
+        let Some(pivot) = candidates
+            .union(&excluded)
+            .max_by_key(|&&vertex| candidates.intersection(&self.neighbours[vertex]).count())
+        else {
+            return;
+        };
+        let to_try: Vec<usize> = candidates
+            .difference(&self.neighbours[*pivot])
+            .copied()
+            .collect();
+
+        for vertex in to_try {
+            let neighbours = &self.neighbours[vertex];
+
+            clique.push(vertex);
+            self.largest_clique(
+                clique,
+                candidates.intersection(neighbours).copied().collect(),
+                excluded.intersection(neighbours).copied().collect(),
+                largest,
+            );
+            clique.pop();
+
+            candidates.remove(&vertex);
+            excluded.insert(vertex);
+        }
+    }
+
+    /// Returns the names in the largest clique, sorted and joined by commas.
+    /// If several cliques share the largest size, any of them may be returned.
+    fn password(&self) -> String {
+        let mut largest = Vec::new();
+        self.largest_clique(
+            &mut Vec::new(),
+            (0..self.names.len()).collect(),
+            HashSet::new(),
+            &mut largest,
+        );
+
+        let mut names: Vec<&str> = largest.iter().map(|&i| self.names[i]).collect();
+        names.sort_unstable();
+        names.join(",")
+    }
+}
+
+impl Puzzle for Day23 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(network) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = Some(network.count_t_triangles().to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(network) = parse_input(input) else {
+            return;
+        };
+
+        self.part_2_result = Some(network.password());
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        23
+    }
+}
@@ -0,0 +1,430 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::adventofcode::Puzzle;
+
+/// The number of gate pairs whose outputs were swapped in the adder.
+const SWAPPED_PAIRS: usize = 4;
+
+#[derive(Default)]
+pub struct Day24 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day24 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+impl Operation {
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "AND" => Some(Operation::And),
+            "OR" => Some(Operation::Or),
+            "XOR" => Some(Operation::Xor),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::And => "AND",
+            Operation::Or => "OR",
+            Operation::Xor => "XOR",
+        }
+    }
+
+    fn apply(&self, a: bool, b: bool) -> bool {
+        match self {
+            Operation::And => a && b,
+            Operation::Or => a || b,
+            Operation::Xor => a ^ b,
+        }
+    }
+}
+
+struct Gate<'a> {
+    inputs: [&'a str; 2],
+    operation: Operation,
+    output: &'a str,
+}
+
+struct Circuit<'a> {
+    initial: HashMap<&'a str, bool>,
+    gates: Vec<Gate<'a>>,
+}
+
+/// Returns the initial wire values and the gates, or `None` if the input is
+/// malformed or a wire is driven more than once.
+fn parse_input(input: &str) -> Option<Circuit<'_>> {
+    let mut lines = input.lines();
+
+    let mut initial = HashMap::new();
+    for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+        let (wire, value) = line.split_once(": ")?;
+        let value = match value {
+            "0" => false,
+            "1" => true,
+            _ => return None,
+        };
+        if initial.insert(wire, value).is_some() {
+            return None;
+        }
+    }
+
+    let mut gates = Vec::new();
+    for line in lines {
+        let [a, operation, b, "->", output] = line.split(' ').collect::<Vec<_>>()[..] else {
+            return None;
+        };
+        if initial.contains_key(output) {
+            return None;
+        }
+        gates.push(Gate {
+            inputs: [a, b],
+            operation: Operation::from_str(operation)?,
+            output,
+        });
+    }
+
+    let mut outputs: Vec<&str> = gates.iter().map(|gate| gate.output).collect();
+    outputs.sort_unstable();
+    outputs.dedup();
+    if outputs.len() != gates.len() {
+        return None;
+    }
+
+    Some(Circuit { initial, gates })
+}
+
+impl<'a> Circuit<'a> {
+    /// Evaluates every gate in topological order, starting with the gates whose
+    /// inputs are all initial wires. Returns `None` if some gate can never be
+    /// evaluated, because of a loop or an input wire that does not exist.
+    fn evaluate(&self) -> Option<HashMap<&'a str, bool>> {
+        let mut values = self.initial.clone();
+        let mut consumers: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut pending = vec![0; self.gates.len()];
+        let mut queue = VecDeque::new();
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            for input in gate.inputs {
+                if !values.contains_key(input) {
+                    consumers.entry(input).or_default().push(i);
+                    pending[i] += 1;
+                }
+            }
+            if pending[i] == 0 {
+                queue.push_back(i);
+            }
+        }
+
+        let mut evaluated = 0;
+        while let Some(i) = queue.pop_front() {
+            let gate = &self.gates[i];
+            let [a, b] = gate.inputs.map(|input| values[input]);
+            values.insert(gate.output, gate.operation.apply(a, b));
+            evaluated += 1;
+
+            for &consumer in consumers.get(gate.output).into_iter().flatten() {
+                pending[consumer] -= 1;
+                if pending[consumer] == 0 {
+                    queue.push_back(consumer);
+                }
+            }
+        }
+
+        (evaluated == self.gates.len()).then_some(values)
+    }
+
+    /// Returns the number formed by the `z` wires, or `None` if the circuit
+    /// cannot be evaluated or has a `z` wire beyond the 64th bit.
+    fn z_number(&self) -> Option<u64> {
+        self.evaluate()?
+            .iter()
+            .filter(|(wire, _)| wire.starts_with('z'))
+            .try_fold(0u64, |number, (wire, &value)| {
+                let bit: u32 = wire[1..].parse().ok().filter(|&bit| bit < 64)?;
+                Some(number | (value as u64) << bit)
+            })
+    }
+
+    /// Returns the outputs of all gates that break the structure of a
+    /// ripple-carry adder.
+    ///
+    /// For every bit after the first the adder computes `x XOR y` and
+    /// `x AND y`, combines the first one with the incoming carry through
+    /// another XOR into `z` and another AND, and joins both ANDs with an OR
+    /// into the outgoing carry. The last carry is the highest `z` wire. A
+    /// swapped output shows up as a gate that cannot be in that position:
+    /// - a `z` wire that is not written by an XOR, or the highest `z` wire
+    ///   that is not written by an OR
+    /// - an XOR that neither reads `x` and `y` nor writes a `z` wire
+    /// - an XOR of `x` and `y` whose result does not go into another XOR
+    /// - an AND whose result does not go into an OR
+    fn misplaced_outputs(&self) -> Vec<&'a str> {
+        let highest_z = self
+            .gates
+            .iter()
+            .map(|gate| gate.output)
+            .filter(|wire| wire.starts_with('z'))
+            .max()
+            .unwrap_or_default();
+        let is_first_bit = |gate: &Gate| gate.inputs.contains(&"x00");
+        let reads_inputs = |gate: &Gate| {
+            gate.inputs
+                .iter()
+                .all(|input| input.starts_with('x') || input.starts_with('y'))
+        };
+        let feeds = |wire: &str, operation: Operation| {
+            self.gates
+                .iter()
+                .any(|gate| gate.operation == operation && gate.inputs.contains(&wire))
+        };
+
+        let mut wires: Vec<&str> = self
+            .gates
+            .iter()
+            .filter(|gate| {
+                let writes_z = gate.output.starts_with('z');
+                match gate.operation {
+                    _ if gate.output == highest_z => gate.operation != Operation::Or,
+                    Operation::Xor if reads_inputs(gate) => {
+                        !is_first_bit(gate) && !feeds(gate.output, Operation::Xor)
+                    }
+                    Operation::Xor => !writes_z,
+                    Operation::And => {
+                        writes_z || !is_first_bit(gate) && !feeds(gate.output, Operation::Or)
+                    }
+                    Operation::Or => writes_z,
+                }
+            })
+            .map(|gate| gate.output)
+            .collect();
+
+        wires.sort_unstable();
+        wires
+    }
+
+    /// Renders the circuit as a Graphviz DOT graph. Every gate is a node named
+    /// after its output wire and labelled with its operation.
+    fn to_dot(&self) -> String {
+        let mut lines = vec!["digraph circuit {".to_string()];
+
+        let mut initial: Vec<&&str> = self.initial.keys().collect();
+        initial.sort_unstable();
+        for wire in initial {
+            lines.push(format!("    {} [shape=box];", wire));
+        }
+        for gate in &self.gates {
+            let shape = if gate.output.starts_with('z') {
+                "doubleoctagon"
+            } else {
+                "ellipse"
+            };
+            lines.push(format!(
+                "    {} [label=\"{}\\n{}\", shape={}];",
+                gate.output,
+                gate.output,
+                gate.operation.name(),
+                shape
+            ));
+            for input in gate.inputs {
+                lines.push(format!("    {} -> {};", input, gate.output));
+            }
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+/// Exports the circuit in the puzzle input as a Graphviz DOT graph, e.g. to
+/// inspect the adder with `dot -Tsvg`. Returns `None` if the input cannot be parsed.
+pub fn to_dot(input: &str) -> Option<String> {
+    Some(parse_input(input)?.to_dot())
+}
+
+impl Puzzle for Day24 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(circuit) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = circuit.z_number().map(|x| x.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(circuit) = parse_input(input) else {
+            return;
+        };
+
+        let wires = circuit.misplaced_outputs();
+        if wires.len() == SWAPPED_PAIRS * 2 {
+            self.part_2_result = Some(wires.join(","));
+        }
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        24
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filehelper::read_test_input;
+
+    use super::*;
+
+    /// Builds a ripple-carry adder for two numbers of the given number of bits,
+    /// with the outputs of the given pairs of wires swapped.
+    fn adder_input(bits: usize, x: u64, y: u64, swaps: &[(&str, &str)]) -> String {
+        let mut lines = Vec::new();
+        for (name, value) in [("x", x), ("y", y)] {
+            for bit in 0..bits {
+                lines.push(format!("{}{:02}: {}", name, bit, value >> bit & 1));
+            }
+        }
+        lines.push(String::new());
+
+        let carry = |bit: usize| {
+            if bit == bits - 1 {
+                format!("z{:02}", bits)
+            } else {
+                format!("c{:02}", bit)
+            }
+        };
+        let mut gates = vec![
+            ("x00 XOR y00".to_string(), "z00".to_string()),
+            ("x00 AND y00".to_string(), carry(0)),
+        ];
+        for bit in 1..bits {
+            gates.extend([
+                (format!("x{0:02} XOR y{0:02}", bit), format!("s{:02}", bit)),
+                (format!("x{0:02} AND y{0:02}", bit), format!("a{:02}", bit)),
+                (
+                    format!("s{:02} XOR {}", bit, carry(bit - 1)),
+                    format!("z{:02}", bit),
+                ),
+                (
+                    format!("s{:02} AND {}", bit, carry(bit - 1)),
+                    format!("b{:02}", bit),
+                ),
+                (format!("a{0:02} OR b{0:02}", bit), carry(bit)),
+            ]);
+        }
+
+        for (inputs, output) in gates {
+            let output = swaps
+                .iter()
+                .find_map(|&(a, b)| {
+                    if output == a {
+                        Some(b.to_string())
+                    } else if output == b {
+                        Some(a.to_string())
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(output);
+            lines.push(format!("{} -> {}", inputs, output));
+        }
+
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_day24_part_1_adder() {
+        let mut puzzle = Day24::new();
+        let input = adder_input(44, 12345678901234, 9876543210987, &[]);
+
+        puzzle.execute_part_1(&input);
+
+        let result = puzzle.get_result_part_1();
+        assert_eq!(
+            result,
+            Some((12345678901234u64 + 9876543210987).to_string())
+        );
+    }
+
+    #[test]
+    fn test_day24_part_2() {
+        let mut puzzle = Day24::new();
+        let swaps = [
+            ("z05", "b05"),
+            ("s10", "a10"),
+            ("z20", "c20"),
+            ("z30", "a30"),
+        ];
+        let input = adder_input(44, 1, 2, &swaps);
+
+        puzzle.execute_part_2(&input);
+
+        let result = puzzle.get_result_part_2();
+        assert_eq!(result, Some("a10,a30,b05,c20,s10,z05,z20,z30".to_string()));
+    }
+
+    #[test]
+    fn test_day24_part_2_correct_adder() {
+        let mut puzzle = Day24::new();
+        let input = adder_input(44, 1, 2, &[]);
+
+        puzzle.execute_part_2(&input);
+
+        assert_eq!(puzzle.get_result_part_2(), None);
+    }
+
+    #[test]
+    fn test_day24_loop() {
+        let mut puzzle = Day24::new();
+
+        puzzle.execute_part_1("x00: 1\n\nx00 AND a -> b\nx00 OR b -> a");
+
+        assert_eq!(puzzle.get_result_part_1(), None);
+    }
+
+    #[test]
+    fn test_day24_to_dot() {
+        let input = read_test_input(24, None, Some(1));
+
+        let expected = [
+            "digraph circuit {",
+            "    x00 [shape=box];",
+            "    x01 [shape=box];",
+            "    x02 [shape=box];",
+            "    y00 [shape=box];",
+            "    y01 [shape=box];",
+            "    y02 [shape=box];",
+            "    z00 [label=\"z00\\nAND\", shape=doubleoctagon];",
+            "    x00 -> z00;",
+            "    y00 -> z00;",
+            "    z01 [label=\"z01\\nXOR\", shape=doubleoctagon];",
+            "    x01 -> z01;",
+            "    y01 -> z01;",
+            "    z02 [label=\"z02\\nOR\", shape=doubleoctagon];",
+            "    x02 -> z02;",
+            "    y02 -> z02;",
+            "}",
+        ];
+        assert_eq!(to_dot(&input), Some(expected.join("\n")));
+    }
+}
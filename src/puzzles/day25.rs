@@ -0,0 +1,120 @@
+use crate::adventofcode::Puzzle;
+
+#[derive(Default)]
+pub struct Day25 {
+    part_1_result: Option<String>,
+    part_2_result: Option<String>,
+}
+
+impl Day25 {
+    pub fn new() -> Self {
+        Self {
+            part_1_result: None,
+            part_2_result: None,
+        }
+    }
+}
+
+/// The locks and keys as column heights, all from schematics of the same size.
+struct Schematics {
+    locks: Vec<Vec<usize>>,
+    keys: Vec<Vec<usize>>,
+    /// The height a lock and a key may add up to in a column without overlapping.
+    space: usize,
+}
+
+/// Reads the schematics, or `None` if they differ in size or one is neither a lock
+/// (top row filled, bottom row empty) nor a key (the other way around).
+fn parse_input(input: &str) -> Option<Schematics> {
+    let mut schematics = Schematics {
+        locks: Vec::new(),
+        keys: Vec::new(),
+        space: 0,
+    };
+    let mut size = None;
+
+    for block in input.split("\n\n").filter(|block| !block.trim().is_empty()) {
+        let rows: Vec<&[u8]> = block.lines().map(str::as_bytes).collect();
+        let width = rows[0].len();
+        if rows.len() < 2
+            || width == 0
+            || rows
+                .iter()
+                .any(|row| row.len() != width || row.iter().any(|&b| b != b'#' && b != b'.'))
+        {
+            return None;
+        }
+        if *size.get_or_insert((width, rows.len())) != (width, rows.len()) {
+            return None;
+        }
+
+        let filled = |row: &[u8]| row.iter().all(|&b| b == b'#');
+        let empty = |row: &[u8]| row.iter().all(|&b| b == b'.');
+        let (first, last) = (rows[0], rows[rows.len() - 1]);
+        let is_lock = if filled(first) && empty(last) {
+            true
+        } else if empty(first) && filled(last) {
+            false
+        } else {
+            return None;
+        };
+
+        // The filled top or bottom row is the base, not part of the height
+        let heights = (0..width)
+            .map(|x| rows.iter().filter(|row| row[x] == b'#').count() - 1)
+            .collect();
+        if is_lock {
+            schematics.locks.push(heights);
+        } else {
+            schematics.keys.push(heights);
+        }
+    }
+
+    schematics.space = size?.1 - 2;
+    Some(schematics)
+}
+
+impl Schematics {
+    /// Counts the lock and key pairs that do not overlap in any column.
+    fn count_fitting_pairs(&self) -> usize {
+        self.locks
+            .iter()
+            .map(|lock| {
+                self.keys
+                    .iter()
+                    .filter(|key| {
+                        lock.iter()
+                            .zip(key.iter())
+                            .all(|(l, k)| l + k <= self.space)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl Puzzle for Day25 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(schematics) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = Some(schematics.count_fitting_pairs().to_string());
+    }
+
+    fn execute_part_2(&mut self, _input: &str) {
+        // Day 25 only has one puzzle
+    }
+
+    fn get_result_part_1(&self) -> Option<String> {
+        self.part_1_result.clone()
+    }
+
+    fn get_result_part_2(&self) -> Option<String> {
+        self.part_2_result.clone()
+    }
+
+    fn day(&self) -> u32 {
+        25
+    }
+}
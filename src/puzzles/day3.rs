@@ -2,6 +2,7 @@ use regex::Regex;
 
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day3 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -18,22 +19,22 @@ impl Day3 {
 
 impl Puzzle for Day3 {
     fn execute_part_1(&mut self, input: &str) {
-        let pattern = r"mul\((\d+),(\d+)\)";
+        let pattern = r"mul\((\d{1,3}),(\d{1,3})\)";
         let re = Regex::new(pattern).unwrap();
-        let sum: i32 = re
+        let sum: i64 = re
             .captures_iter(input)
-            .map(|x| x[1].parse::<i32>().unwrap() * x[2].parse::<i32>().unwrap())
+            .map(|x| x[1].parse::<i64>().unwrap() * x[2].parse::<i64>().unwrap())
             .sum();
 
         self.part_1_result = Some(sum.to_string());
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let pattern = r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)";
+        let pattern = r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)";
         let re = Regex::new(pattern).unwrap();
 
         let mut skip = false;
-        let sum: i32 = re
+        let sum: i64 = re
             .captures_iter(input)
             .map(|x| {
                 let func_name = x.get(0).unwrap().as_str();
@@ -43,7 +44,7 @@ impl Puzzle for Day3 {
                 } else if func_name.contains("do") {
                     skip = false;
                 } else if func_name.contains("mul") && !skip {
-                    return x[1].parse::<i32>().unwrap() * x[2].parse::<i32>().unwrap();
+                    return x[1].parse::<i64>().unwrap() * x[2].parse::<i64>().unwrap();
                 }
                 0
             })
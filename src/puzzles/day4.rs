@@ -1,5 +1,7 @@
 use crate::adventofcode::Puzzle;
+use crate::grid::{parse_grid, Grid};
 
+#[derive(Default)]
 pub struct Day4 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -80,11 +82,14 @@ fn get_indexes(
 
 impl Puzzle for Day4 {
     fn execute_part_1(&mut self, input: &str) {
-        let lines_str = input.split('\n');
-
-        let height = lines_str.clone().count() as i32;
-        let width = lines_str.clone().next().unwrap().len() as i32;
-        let map: Vec<char> = input.lines().collect::<String>().chars().collect();
+        let Some(Grid {
+            tiles: map,
+            width,
+            height,
+        }) = parse_grid(input)
+        else {
+            return;
+        };
 
         let xmas = "XMAS";
         let steps: i32 = xmas.len() as i32 - 1;
@@ -116,11 +121,14 @@ impl Puzzle for Day4 {
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let lines_str = input.split('\n');
-        let height = lines_str.clone().count() as i32;
-        let width = lines_str.clone().next().unwrap().len() as i32;
-
-        let map: Vec<char> = input.lines().collect::<String>().chars().collect();
+        let Some(Grid {
+            tiles: map,
+            width,
+            height,
+        }) = parse_grid(input)
+        else {
+            return;
+        };
 
         let mut result = 0;
         for (i, c) in map.iter().enumerate() {
@@ -135,7 +143,7 @@ impl Puzzle for Day4 {
                     let ne = get_indexes(&Direction::NE, 1, x, y, width, height);
                     let sw = get_indexes(&Direction::SW, 1, x, y, width, height);
 
-                    if !nw.is_empty() && !se.is_empty() && !nw.is_empty() && !se.is_empty() {
+                    if !nw.is_empty() && !se.is_empty() && !ne.is_empty() && !sw.is_empty() {
                         let a1 = *map.get(*nw.last().unwrap()).unwrap();
                         let b1 = *map.get(*se.last().unwrap()).unwrap();
 
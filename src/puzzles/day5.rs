@@ -1,13 +1,12 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day5 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
-    page_ordering_rules: HashMap<u32, Vec<u32>>,
-    rev_page_ordering_rules: HashMap<u32, Vec<u32>>,
-    page_numbers_list: Vec<Vec<u32>>,
 }
 
 impl Day5 {
@@ -15,106 +14,256 @@ impl Day5 {
         Self {
             part_1_result: None,
             part_2_result: None,
-            page_ordering_rules: HashMap::new(),
-            rev_page_ordering_rules: HashMap::new(),
-            page_numbers_list: Vec::new(),
         }
     }
+}
+
+/// Reasons why the pages of an update cannot be put in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderingError {
+    /// The page appears more than once in the update.
+    DuplicatePage(u32),
+    /// The rules between these pages form a cycle, each page has to come
+    /// before the next one and the last one before the first.
+    Cycle(Vec<u32>),
+    /// Neither the rules nor the other pages decide which of the two pages comes first.
+    Ambiguous(u32, u32),
+}
 
-    fn sort_pages(&self, next_page: &u32, current_page: &u32) -> Ordering {
-        // Check if the current page has a page ordering rule
-        if let Some(page_ordering_rule) = self.page_ordering_rules.get(current_page) {
-            if page_ordering_rule.contains(next_page) {
-                // Current page has a page ordering rule
-                return std::cmp::Ordering::Equal;
+impl fmt::Display for OrderingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingError::DuplicatePage(page) => write!(f, "page {} appears more than once", page),
+            OrderingError::Cycle(pages) => {
+                let pages: Vec<String> = pages.iter().map(|page| page.to_string()).collect();
+                write!(f, "the rules form a cycle {}|{}", pages.join("|"), pages[0])
+            }
+            OrderingError::Ambiguous(a, b) => {
+                write!(f, "there is no order between pages {} and {}", a, b)
             }
         }
+    }
+}
+
+impl std::error::Error for OrderingError {}
+
+/// The page ordering rules as a directed graph, with an edge from every page
+/// to each page that must come after it.
+#[derive(Debug, Default)]
+pub struct PageOrderingRules {
+    after: HashMap<u32, HashSet<u32>>,
+}
+
+impl PageOrderingRules {
+    fn must_precede(&self, before: u32, after: u32) -> bool {
+        self.after
+            .get(&before)
+            .is_some_and(|pages| pages.contains(&after))
+    }
 
-        // If not, check what other page points to the current page
-        if let Some(what_points_to_this) = self.rev_page_ordering_rules.get(current_page) {
-            if what_points_to_this.contains(next_page) {
-                // Yes, it points to the current page therefore we need to switch them.
-                return std::cmp::Ordering::Less;
+    /// Puts the pages of an update in order with Kahn's algorithm, only using
+    /// the rules between pages of the update.
+    ///
+    /// The order has to be unique, so at every step exactly one page may be
+    /// left that no other remaining page has to come before.
+    pub fn order(&self, pages: &[u32]) -> Result<Vec<u32>, OrderingError> {
+        let mut in_degrees: HashMap<u32, usize> = HashMap::new();
+        for &page in pages {
+            if in_degrees.insert(page, 0).is_some() {
+                return Err(OrderingError::DuplicatePage(page));
+            }
+        }
+        for &before in pages {
+            for &after in pages {
+                if self.must_precede(before, after) {
+                    *in_degrees.get_mut(&after).unwrap() += 1;
+                }
             }
         }
 
-        panic!("This should not happen?");
+        let mut ready: Vec<u32> = pages
+            .iter()
+            .copied()
+            .filter(|page| in_degrees[page] == 0)
+            .collect();
+        let mut ordered = Vec::with_capacity(pages.len());
+
+        while let Some(page) = ready.pop() {
+            if let Some(&other) = ready.last() {
+                return Err(OrderingError::Ambiguous(other, page));
+            }
+            ordered.push(page);
+
+            for &after in pages {
+                if self.must_precede(page, after) {
+                    let in_degree = in_degrees.get_mut(&after).unwrap();
+                    *in_degree -= 1;
+                    if *in_degree == 0 {
+                        ready.push(after);
+                    }
+                }
+            }
+        }
+
+        if ordered.len() < pages.len() {
+            let remaining: Vec<u32> = pages
+                .iter()
+                .copied()
+                .filter(|page| !ordered.contains(page))
+                .collect();
+            return Err(OrderingError::Cycle(self.find_cycle(&remaining)));
+        }
+
+        Ok(ordered)
     }
 
-    fn init_rules(&mut self, input: &str) {
-        self.page_ordering_rules = input
-            .lines()
-            .filter_map(|line| line.split_once('|'))
-            .map(|(left, right)| {
-                let left = left.trim().parse::<u32>().unwrap();
-                let right = right.trim().parse::<u32>().unwrap();
-                (left, right)
-            })
-            .fold(HashMap::new(), |mut map, (left, right)| {
-                map.entry(left).or_default().push(right);
-                map
-            });
-
-        self.rev_page_ordering_rules = input
-            .lines()
-            .filter_map(|line| line.split_once('|'))
-            .map(|(left, right)| {
-                let left = left.trim().parse::<u32>().unwrap();
-                let right = right.trim().parse::<u32>().unwrap();
-                (left, right)
-            })
-            .fold(HashMap::new(), |mut map, (left, right)| {
-                map.entry(right).or_default().push(left);
-                map
-            });
-
-        self.page_numbers_list = input
-            .lines()
-            .filter(|line| line.contains(','))
-            .map(|line| {
-                line.split(',')
-                    .map(|x| x.trim().parse::<u32>().unwrap())
-                    .collect()
+    /// Finds a cycle among pages where every page has to come after another one of them.
+    ///
+    /// Walks backwards from any page until a page repeats, which always
+    /// happens since every page has a predecessor among them.
+    fn find_cycle(&self, pages: &[u32]) -> Vec<u32> {
+        let mut path = vec![pages[0]];
+        loop {
+            let page = *path.last().unwrap();
+            let before = *pages
+                .iter()
+                .find(|&&before| self.must_precede(before, page))
+                .unwrap();
+
+            if let Some(start) = path.iter().position(|&visited| visited == before) {
+                let mut cycle = path.split_off(start);
+                cycle.reverse();
+                // Start at the lowest page, so the same cycle is always reported the same way
+                let lowest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+                cycle.rotate_left(lowest);
+                return cycle;
+            }
+            path.push(before);
+        }
+    }
+
+    /// Returns the rules the update breaks, as `(before, after)` pairs of pages
+    /// where `after` comes first in the update.
+    pub fn violated_rules(&self, pages: &[u32]) -> Vec<(u32, u32)> {
+        pages
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &first)| {
+                pages[i + 1..]
+                    .iter()
+                    .filter(move |&&second| self.must_precede(second, first))
+                    .map(move |&second| (second, first))
             })
-            .collect();
+            .collect()
     }
 }
 
-impl Puzzle for Day5 {
-    fn execute_part_1(&mut self, input: &str) {
-        self.init_rules(input);
+/// The page ordering rules and the pages of every update.
+pub struct PrintQueue {
+    pub rules: PageOrderingRules,
+    pub updates: Vec<Vec<u32>>,
+}
 
-        let mut result = 0;
-        for page_numbers in &self.page_numbers_list {
-            let mut page_numbers_copy = page_numbers.clone();
-            page_numbers_copy
-                .sort_by(|next_page, current_page| self.sort_pages(next_page, current_page));
+/// Parses the rules and updates, or returns `None` if the input is malformed.
+pub fn parse_input(input: &str) -> Option<PrintQueue> {
+    let mut rules = PageOrderingRules::default();
+    for (before, after) in input.lines().filter_map(|line| line.split_once('|')) {
+        let before = before.trim().parse::<u32>().ok()?;
+        let after = after.trim().parse::<u32>().ok()?;
+        rules.after.entry(before).or_default().insert(after);
+    }
 
-            if page_numbers == &page_numbers_copy {
-                result += page_numbers.get((page_numbers.len() - 1) / 2).unwrap();
-            }
+    let updates = input
+        .lines()
+        .filter(|line| line.contains(','))
+        .map(|line| {
+            line.split(',')
+                .map(|x| x.trim().parse::<u32>().ok())
+                .collect()
+        })
+        .collect::<Option<_>>()?;
+
+    Some(PrintQueue { rules, updates })
+}
+
+/// Sums the middle pages of the updates that were already in order, or of
+/// the ones that had to be reordered. Returns `None` if an update that breaks
+/// a rule cannot be put in order or the sum does not fit in a `u32`.
+fn sum_middle_pages(queue: &PrintQueue, reordered: bool) -> Option<u32> {
+    let mut result = 0;
+    for pages in &queue.updates {
+        let in_order = queue.rules.violated_rules(pages).is_empty();
+        if reordered == in_order {
+            continue;
         }
 
-        self.part_1_result = Some(result.to_string());
+        let middle = if in_order {
+            pages[(pages.len() - 1) / 2]
+        } else {
+            let ordered = queue.rules.order(pages).ok()?;
+            ordered[(ordered.len() - 1) / 2]
+        };
+        result = middle.checked_add(result)?;
     }
 
-    fn execute_part_2(&mut self, input: &str) {
-        self.init_rules(input);
-
-        let mut result = 0;
-        for page_numbers in &self.page_numbers_list {
-            let mut page_numbers_copy = page_numbers.clone();
-            page_numbers_copy
-                .sort_by(|next_page, current_page| self.sort_pages(next_page, current_page));
-
-            if page_numbers != &page_numbers_copy {
-                result += page_numbers_copy
-                    .get((page_numbers_copy.len() - 1) / 2)
-                    .unwrap();
+    Some(result)
+}
+
+/// Shows for every update whether it is in order, and otherwise which rules
+/// it breaks and how it is reordered, or why it cannot be ordered.
+///
+/// Returns `None` if the input cannot be parsed.
+pub fn explain(input: &str) -> Option<String> {
+    let queue = parse_input(input)?;
+    let join = |pages: &[u32]| {
+        let pages: Vec<String> = pages.iter().map(|page| page.to_string()).collect();
+        pages.join(",")
+    };
+
+    let lines: Vec<String> = queue
+        .updates
+        .iter()
+        .map(|pages| {
+            let violated = queue.rules.violated_rules(pages);
+            if violated.is_empty() {
+                return format!("{}: in order", join(pages));
             }
-        }
+            let ordered = match queue.rules.order(pages) {
+                Ok(ordered) => ordered,
+                Err(err) => return format!("{}: cannot be ordered, {}", join(pages), err),
+            };
+
+            let violated: Vec<String> = violated
+                .iter()
+                .map(|(before, after)| format!("{}|{}", before, after))
+                .collect();
+            format!(
+                "{}: breaks {}, reordered to {}",
+                join(pages),
+                violated.join(" "),
+                join(&ordered)
+            )
+        })
+        .collect();
+
+    Some(lines.join("\n"))
+}
+
+impl Puzzle for Day5 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(queue) = parse_input(input) else {
+            return;
+        };
+
+        self.part_1_result = sum_middle_pages(&queue, false).map(|x| x.to_string());
+    }
+
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(queue) = parse_input(input) else {
+            return;
+        };
 
-        self.part_2_result = Some(result.to_string());
+        self.part_2_result = sum_middle_pages(&queue, true).map(|x| x.to_string());
     }
 
     fn get_result_part_1(&self) -> Option<String> {
@@ -179,4 +328,49 @@ mod tests {
         let result = puzzle.get_result_part_2();
         assert_eq!(result, Some("6004".to_string()));
     }
+
+    #[test]
+    fn test_day5_ordering_errors() {
+        let queue = parse_input("1|2\n2|3\n3|1\n4|5\n\n1,2,3\n4,5,6\n4,5,4").unwrap();
+
+        assert_eq!(
+            queue.rules.order(&queue.updates[0]),
+            Err(OrderingError::Cycle(vec![1, 2, 3]))
+        );
+        assert_eq!(
+            queue.rules.order(&queue.updates[1]),
+            Err(OrderingError::Ambiguous(4, 6))
+        );
+        assert_eq!(
+            queue.rules.order(&queue.updates[2]),
+            Err(OrderingError::DuplicatePage(4))
+        );
+    }
+
+    #[test]
+    fn test_day5_unordered_pages() {
+        let mut puzzle = Day5::new();
+        let input = "47|53\n\n47,53,1\n53,47,1";
+
+        puzzle.execute_part_1(input);
+        puzzle.execute_part_2(input);
+
+        // No rule decides where page 1 goes, which only matters when reordering
+        assert_eq!(puzzle.get_result_part_1(), Some("53".to_string()));
+        assert_eq!(puzzle.get_result_part_2(), None);
+    }
+
+    #[test]
+    fn test_day5_explain() {
+        let input = read_test_input(5, None, None);
+
+        let explanation = explain(&input).unwrap();
+
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines[0], "75,47,61,53,29: in order");
+        assert_eq!(
+            lines[3],
+            "75,97,47,61,53: breaks 97|75, reordered to 97,75,47,61,53"
+        );
+    }
 }
@@ -1,7 +1,10 @@
 use std::collections::HashSet;
+use std::num::NonZeroUsize;
 
 use crate::adventofcode::Puzzle;
+use crate::grid::parse_grid;
 
+#[derive(Default)]
 pub struct Day6 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -16,12 +19,12 @@ impl Day6 {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
     N,
+    E,
     S,
     W,
-    E,
 }
 
 impl Direction {
@@ -33,147 +36,228 @@ impl Direction {
             Direction::E => (1, 0),
         }
     }
+
+    fn turn_right(self) -> Self {
+        match self {
+            Direction::N => Direction::E,
+            Direction::E => Direction::S,
+            Direction::S => Direction::W,
+            Direction::W => Direction::N,
+        }
+    }
+}
+
+/// Where a straight walk ends: in front of an obstacle, or on the last cell
+/// before the guard leaves the map.
+#[derive(Debug, Clone, Copy)]
+enum Leg {
+    Stop(usize),
+    Exit(usize),
 }
 
-fn filter_direction(
-    obstacles: &[(i32, i32)],
-    start_pos: (i32, i32),
-    direction: Direction,
-) -> Option<&(i32, i32)> {
-    match direction {
-        Direction::N => obstacles
-            .iter()
-            .filter(|(x, y)| *x == start_pos.0 && start_pos.1 > *y)
-            .min_by_key(|(_x, y)| (*y - start_pos.1).abs()),
-        Direction::S => obstacles
-            .iter()
-            .filter(|(x, y)| *x == start_pos.0 && start_pos.1 < *y)
-            .min_by_key(|(_x, y)| (*y - start_pos.1).abs()),
-        Direction::E => obstacles
-            .iter()
-            .filter(|(x, y)| *x > start_pos.0 && start_pos.1 == *y)
-            .min_by_key(|(x, _y)| (*x - start_pos.0).abs()),
-        Direction::W => obstacles
-            .iter()
-            .filter(|(x, y)| *x < start_pos.0 && start_pos.1 == *y)
-            .min_by_key(|(x, _y)| (*x - start_pos.0).abs()),
+/// The lab map, with every cell referred to by its index `y * width + x`.
+struct Lab {
+    width: usize,
+    height: usize,
+    guard: usize,
+    /// For every cell and direction, the cell in front of the next obstacle,
+    /// or `None` if there is no obstacle ahead.
+    jumps: Vec<[Option<usize>; 4]>,
+}
+
+/// Reads the map, or `None` if it is not rectangular or has no guard.
+fn parse_input(input: &str) -> Option<Lab> {
+    let grid = parse_grid(input)?;
+    let (width, height) = (grid.width as usize, grid.height as usize);
+    let guard = grid.tiles.iter().position(|&c| c == '^')?;
+    let blocked: Vec<bool> = grid.tiles.iter().map(|&c| c == '#').collect();
+
+    let mut lab = Lab {
+        width,
+        height,
+        guard,
+        jumps: vec![[None; 4]; grid.tiles.len()],
+    };
+
+    // Sweep every row and column against the walking direction, so the
+    // nearest obstacle ahead of each cell has been seen when the cell is reached
+    let mut sweep = |direction: Direction, line: &mut dyn Iterator<Item = usize>| {
+        let mut stop = None;
+        let mut previous_blocked = false;
+        for cell in line {
+            if previous_blocked {
+                stop = Some(cell);
+            }
+            lab.jumps[cell][direction as usize] = stop;
+            previous_blocked = blocked[cell];
+        }
+    };
+    for x in 0..width {
+        sweep(Direction::N, &mut (0..height).map(|y| y * width + x));
+        sweep(Direction::S, &mut (0..height).rev().map(|y| y * width + x));
     }
+    for y in 0..height {
+        sweep(Direction::W, &mut (0..width).map(|x| y * width + x));
+        sweep(Direction::E, &mut (0..width).rev().map(|x| y * width + x));
+    }
+
+    Some(lab)
 }
 
-fn run_simulation(
-    obstacles: &[(i32, i32)],
-    start_pos: (i32, i32),
-    width: i32,
-    height: i32,
-) -> Option<HashSet<(i32, i32)>> {
-    let mut places: HashSet<(i32, i32)> = HashSet::new();
-    let path = vec![Direction::N, Direction::E, Direction::S, Direction::W];
-    let mut directions = path.into_iter().cycle();
-    let mut current_pos = start_pos;
-
-    let mut last_size = 0;
-    let mut loop_count = 0;
-    loop {
-        if let Some(direction) = directions.next() {
-            if let Some(obstacle) = filter_direction(obstacles, current_pos, direction) {
-                let x_steps =
-                    direction.coordinates().0.abs() * ((obstacle.0 - current_pos.0).abs() - 1);
-                let y_steps =
-                    direction.coordinates().1.abs() * ((obstacle.1 - current_pos.1).abs() - 1);
-                let tmp_steps = x_steps + y_steps;
-
-                for i in 0..tmp_steps {
-                    let x = current_pos.0 + direction.coordinates().0 * i;
-                    let y = current_pos.1 + direction.coordinates().1 * i;
-                    places.insert((x, y));
-                }
+impl Lab {
+    fn position(&self, cell: usize) -> (i32, i32) {
+        ((cell % self.width) as i32, (cell / self.width) as i32)
+    }
 
-                current_pos.0 += tmp_steps * direction.coordinates().0;
-                current_pos.1 += tmp_steps * direction.coordinates().1;
+    fn cell(&self, (x, y): (i32, i32)) -> usize {
+        y as usize * self.width + x as usize
+    }
+
+    /// The number of steps from `from` in `direction` until `to`, or `None`
+    /// if `to` is not straight ahead.
+    fn steps_ahead(&self, from: usize, to: usize, direction: Direction) -> Option<i32> {
+        let (from, to) = (self.position(from), self.position(to));
+        let steps = match direction {
+            Direction::N if from.0 == to.0 => from.1 - to.1,
+            Direction::S if from.0 == to.0 => to.1 - from.1,
+            Direction::W if from.1 == to.1 => from.0 - to.0,
+            Direction::E if from.1 == to.1 => to.0 - from.0,
+            _ => return None,
+        };
+        (steps > 0).then_some(steps)
+    }
 
-                if places.len() != last_size {
-                    loop_count = 0;
-                } else {
-                    loop_count += 1;
+    /// Walks straight from `cell` until the next obstacle or the edge of the map,
+    /// taking the temporary `extra_obstacle` into account.
+    fn leg(&self, cell: usize, direction: Direction, extra_obstacle: Option<usize>) -> Leg {
+        let leg = match self.jumps[cell][direction as usize] {
+            Some(stop) => Leg::Stop(stop),
+            None => {
+                let (x, y) = self.position(cell);
+                let (width, height) = (self.width as i32, self.height as i32);
+                Leg::Exit(self.cell(match direction {
+                    Direction::N => (x, 0),
+                    Direction::S => (x, height - 1),
+                    Direction::W => (0, y),
+                    Direction::E => (width - 1, y),
+                }))
+            }
+        };
+
+        let Some(obstacle) = extra_obstacle else {
+            return leg;
+        };
+        let Some(obstacle_steps) = self.steps_ahead(cell, obstacle, direction) else {
+            return leg;
+        };
+        let (Leg::Stop(end) | Leg::Exit(end)) = leg;
+        let leg_steps = self.steps_ahead(cell, end, direction).unwrap_or(0);
+
+        // The extra obstacle is only in the way if it is closer than the end of the leg
+        if obstacle_steps <= leg_steps + 1 {
+            let (x, y) = self.position(cell);
+            let (dx, dy) = direction.coordinates();
+            let steps = obstacle_steps - 1;
+            Leg::Stop(self.cell((x + dx * steps, y + dy * steps)))
+        } else {
+            leg
+        }
+    }
 
-                    if loop_count == 4 {
-                        // If we loop 4 times, we're probably stuck in a loop
+    /// Follows the guard from turn to turn. Returns the cells where the route
+    /// changes direction, from the start to the cell where the guard leaves
+    /// the map, or `None` if the guard ends up walking in a loop.
+    ///
+    /// The guard is in a loop as soon as it turns at the same cell in the same
+    /// direction twice.
+    fn route(&self, extra_obstacle: Option<usize>) -> Option<Vec<usize>> {
+        let mut corners = vec![self.guard];
+        let mut turns = HashSet::new();
+        let mut cell = self.guard;
+        let mut direction = Direction::N;
+
+        loop {
+            match self.leg(cell, direction, extra_obstacle) {
+                Leg::Stop(stop) => {
+                    if !turns.insert((stop, direction)) {
                         return None;
                     }
+                    corners.push(stop);
+                    cell = stop;
+                    direction = direction.turn_right();
                 }
-
-                last_size = places.len();
-            } else {
-                let tmp_steps = match direction {
-                    Direction::N => current_pos.1.abs(),
-                    Direction::S => (current_pos.1 - (height - 1)).abs(),
-                    Direction::W => current_pos.0.abs(),
-                    Direction::E => (current_pos.0 - (width - 1)).abs(),
-                };
-
-                for i in 0..=tmp_steps {
-                    let x = current_pos.0 + direction.coordinates().0 * i;
-                    let y = current_pos.1 + direction.coordinates().1 * i;
-                    places.insert((x, y));
+                Leg::Exit(exit) => {
+                    corners.push(exit);
+                    return Some(corners);
                 }
+            }
+        }
+    }
 
-                current_pos.0 += tmp_steps * direction.coordinates().0;
-                current_pos.1 += tmp_steps * direction.coordinates().1;
-                // End of the map!
-                return Some(places);
+    /// Returns every cell the guard visits before leaving the map, or `None`
+    /// if the guard walks in a loop.
+    fn visited(&self) -> Option<HashSet<usize>> {
+        let corners = self.route(None)?;
+        let mut visited = HashSet::from([self.guard]);
+
+        for pair in corners.windows(2) {
+            let (mut position, end) = (self.position(pair[0]), self.position(pair[1]));
+            let step = ((end.0 - position.0).signum(), (end.1 - position.1).signum());
+            while position != end {
+                position = (position.0 + step.0, position.1 + step.1);
+                visited.insert(self.cell(position));
             }
         }
+
+        Some(visited)
     }
 }
 
 impl Puzzle for Day6 {
     fn execute_part_1(&mut self, input: &str) {
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let map: Vec<char> = input.lines().collect::<String>().chars().collect();
-        let obstacles: Vec<(i32, i32)> = map
-            .iter()
-            .enumerate()
-            .filter(|(_i, c)| **c == '#')
-            .map(|(i, _c)| (i as i32 % width, i as i32 / width))
-            .collect();
-        let start_pos_index = map.iter().enumerate().find(|(_i, c)| **c == '^').unwrap().0 as i32;
-        let start_pos: (i32, i32) = (start_pos_index % width, start_pos_index / width);
+        let Some(lab) = parse_input(input) else {
+            return;
+        };
 
-        if let Some(places) = run_simulation(&obstacles, start_pos, width, height) {
-            self.part_1_result = Some(places.len().to_string());
+        if let Some(visited) = lab.visited() {
+            self.part_1_result = Some(visited.len().to_string());
         }
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let map: Vec<char> = input.lines().collect::<String>().chars().collect();
-        let obstacles: Vec<(i32, i32)> = map
-            .iter()
-            .enumerate()
-            .filter(|(_i, c)| **c == '#')
-            .map(|(i, _c)| (i as i32 % width, i as i32 / width))
+        let Some(lab) = parse_input(input) else {
+            return;
+        };
+        let Some(visited) = lab.visited() else {
+            return;
+        };
+
+        // Only an obstacle on the guard's route can change it
+        let candidates: Vec<usize> = visited
+            .into_iter()
+            .filter(|&cell| cell != lab.guard)
             .collect();
-        let start_pos_index = map.iter().enumerate().find(|(_i, c)| **c == '^').unwrap().0 as i32;
-        let start_pos: (i32, i32) = (start_pos_index % width, start_pos_index / width);
-
-        let mut result = 0;
-        if let Some(mut places) = run_simulation(&obstacles, start_pos, width, height) {
-            // Remove starting position
-            places.retain(|&k| k != start_pos);
-
-            for place in places.iter() {
-                let mut new_obstacles = obstacles.clone();
-                new_obstacles.push(*place);
-
-                let places = run_simulation(&new_obstacles, start_pos, width, height);
-                if places.is_none() {
-                    // None == infinite loop
-                    result += 1;
-                }
-            }
-        }
+
+        let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk_size = candidates.len().div_ceil(threads).max(1);
+        let result: usize = std::thread::scope(|scope| {
+            let handles: Vec<_> = candidates
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let lab = &lab;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .filter(|&&obstacle| lab.route(Some(obstacle)).is_none())
+                            .count()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum()
+        });
 
         self.part_2_result = Some(result.to_string());
     }
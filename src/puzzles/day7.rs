@@ -1,160 +1,319 @@
-use std::collections::HashSet;
-
 use crate::adventofcode::Puzzle;
 
 pub struct Day7 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
+    part_1_operators: Vec<Operator>,
+    part_2_operators: Vec<Operator>,
+}
+
+impl Default for Day7 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Day7 {
     pub fn new() -> Self {
+        Self::with_operators(PART_1_OPERATORS, PART_2_OPERATORS)
+    }
+
+    /// Creates the puzzle with different operators that may be placed between
+    /// the numbers of an equation, for each part.
+    pub fn with_operators(part_1_operators: &[Operator], part_2_operators: &[Operator]) -> Self {
         Self {
             part_1_result: None,
             part_2_result: None,
+            part_1_operators: part_1_operators.to_vec(),
+            part_2_operators: part_2_operators.to_vec(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Operator {
-    Add,
-    Mul,
-    Con,
-}
-
-fn calibration_equation(
-    current_value: i64,
-    remaining: &[i64],
-    operator: Operator,
-    results: &mut HashSet<i64>,
-    goal: i64,
-    allow_concatenation: bool,
-) {
-    if let Some((&next_value, rest)) = remaining.split_first() {
-        let new_value = match operator {
-            Operator::Add => current_value + next_value,
-            Operator::Mul => current_value * next_value,
-            Operator::Con => format!("{}{}", current_value, next_value)
-                .parse::<i64>()
-                .unwrap_or(0),
+/// What the left operand of an operator was, given its result and right operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inverse {
+    /// No left operand gives the result.
+    None,
+    /// Exactly this left operand gives the result.
+    One(i64),
+    /// Every left operand gives the result, like for `x * 0 = 0`.
+    Any,
+}
+
+/// A binary operator that can be placed between the numbers of an equation.
+///
+/// Equations are evaluated from left to right, and an operator that returns
+/// `None` cannot be used with those operands.
+#[derive(Debug, Clone, Copy)]
+pub struct Operator {
+    symbol: &'static str,
+    apply: fn(i64, i64) -> Option<i64>,
+    inverse: Option<fn(i64, i64) -> Inverse>,
+}
+
+impl Operator {
+    pub const ADD: Operator = Operator::new("+", i64::checked_add).with_inverse(|result, right| {
+        result
+            .checked_sub(right)
+            .map_or(Inverse::None, Inverse::One)
+    });
+    pub const MUL: Operator = Operator::new("*", i64::checked_mul).with_inverse(|result, right| {
+        if right == 0 {
+            return if result == 0 {
+                Inverse::Any
+            } else {
+                Inverse::None
+            };
+        }
+        match result.checked_rem(right) {
+            Some(0) => result
+                .checked_div(right)
+                .map_or(Inverse::None, Inverse::One),
+            _ => Inverse::None,
+        }
+    });
+    pub const CON: Operator = Operator::new("||", concatenate).with_inverse(|result, right| {
+        if right < 0 || result < right {
+            return Inverse::None;
+        }
+        // `right` must be the trailing digits of `result`
+        match digit_shift(right) {
+            Some(shift) if (result - right) % shift == 0 => Inverse::One((result - right) / shift),
+            Some(_) => Inverse::None,
+            None if result == right => Inverse::One(0),
+            None => Inverse::None,
+        }
+    });
+    pub const SUB: Operator = Operator::new("-", i64::checked_sub).with_inverse(|result, right| {
+        result
+            .checked_add(right)
+            .map_or(Inverse::None, Inverse::One)
+    });
+    pub const DIV: Operator = Operator::new("/", i64::checked_div);
+    pub const XOR: Operator = Operator::new("^", |left, right| Some(left ^ right))
+        .with_inverse(|result, right| Inverse::One(result ^ right));
+    pub const POW: Operator = Operator::new("**", |left, right| {
+        left.checked_pow(u32::try_from(right).ok()?)
+    });
+
+    /// Creates an operator from the way it is applied to its operands.
+    ///
+    /// Equations with an operator that has no inverse are solved by trying
+    /// every combination of operators from left to right.
+    pub const fn new(symbol: &'static str, apply: fn(i64, i64) -> Option<i64>) -> Self {
+        Self {
+            symbol,
+            apply,
+            inverse: None,
+        }
+    }
+
+    /// Adds the inverse of the operator, which finds the left operand from the
+    /// result and the right operand.
+    ///
+    /// When every operator has an inverse, equations are solved backwards from
+    /// the result, which skips most of the combinations.
+    pub const fn with_inverse(self, inverse: fn(i64, i64) -> Inverse) -> Self {
+        Self {
+            inverse: Some(inverse),
+            ..self
+        }
+    }
+
+    /// Looks up one of the built-in operators, e.g. `+` or `||`.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        BUILT_IN_OPERATORS
+            .iter()
+            .find(|operator| operator.symbol == symbol)
+            .copied()
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+}
+
+/// The operators available in the puzzle.
+pub const PART_1_OPERATORS: &[Operator] = &[Operator::ADD, Operator::MUL];
+pub const PART_2_OPERATORS: &[Operator] = &[Operator::ADD, Operator::MUL, Operator::CON];
+
+/// Every operator that can be looked up with [`Operator::from_symbol`].
+pub const BUILT_IN_OPERATORS: &[Operator] = &[
+    Operator::ADD,
+    Operator::MUL,
+    Operator::CON,
+    Operator::SUB,
+    Operator::DIV,
+    Operator::XOR,
+    Operator::POW,
+];
+
+/// The factor that shifts a number left by the digits of `right`, or `None`
+/// if it does not fit in an `i64`.
+fn digit_shift(right: i64) -> Option<i64> {
+    10i64.checked_pow(right.checked_ilog10().unwrap_or(0) + 1)
+}
+
+/// Joins the digits of both operands, e.g. `12 || 345 = 12345`.
+fn concatenate(left: i64, right: i64) -> Option<i64> {
+    if left < 0 || right < 0 {
+        return None;
+    }
+    if left == 0 {
+        return Some(right);
+    }
+    left.checked_mul(digit_shift(right)?)?.checked_add(right)
+}
+
+/// The most operator combinations an equation may have. Three operators
+/// between the 12 numbers of the longest puzzle equations stay below it,
+/// but larger operator sets quickly make the search take forever.
+pub const MAX_COMBINATIONS: u64 = 1 << 20;
+
+/// An equation has more than [`MAX_COMBINATIONS`] operator combinations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TooManyCombinations;
+
+/// Finds operators that make `values` evaluate to `target` from left to right,
+/// or `None` if there are none.
+fn solve_equation(
+    target: i64,
+    values: &[i64],
+    operators: &[Operator],
+) -> Result<Option<Vec<Operator>>, TooManyCombinations> {
+    let combinations = u32::try_from(values.len().saturating_sub(1))
+        .ok()
+        .and_then(|gaps| (operators.len() as u64).checked_pow(gaps));
+    if combinations.is_none_or(|combinations| combinations > MAX_COMBINATIONS) {
+        return Err(TooManyCombinations);
+    }
+
+    if operators.iter().all(|operator| operator.inverse.is_some()) {
+        Ok(solve_backwards(target, values, operators))
+    } else {
+        let Some((&first, rest)) = values.split_first() else {
+            return Ok(None);
         };
+        Ok(solve_forwards(first, rest, operators, &|result| {
+            result == target
+        }))
+    }
+}
 
-        if new_value <= goal {
-            for &next_operator in &[Operator::Add, Operator::Mul] {
-                calibration_equation(
-                    new_value,
-                    rest,
-                    next_operator,
-                    results,
-                    goal,
-                    allow_concatenation,
-                );
-            }
-            if allow_concatenation {
-                calibration_equation(
-                    new_value,
-                    rest,
-                    Operator::Con,
-                    results,
-                    goal,
-                    allow_concatenation,
-                );
+/// Tries every combination of operators from left to right, starting at
+/// `value`, until the result is accepted.
+fn solve_forwards(
+    value: i64,
+    values: &[i64],
+    operators: &[Operator],
+    accept: &dyn Fn(i64) -> bool,
+) -> Option<Vec<Operator>> {
+    let Some((&next, rest)) = values.split_first() else {
+        return accept(value).then(Vec::new);
+    };
+
+    operators.iter().find_map(|operator| {
+        let value = (operator.apply)(value, next)?;
+        let mut solution = solve_forwards(value, rest, operators, accept)?;
+        solution.insert(0, *operator);
+        Some(solution)
+    })
+}
+
+/// Works backwards from the last value, undoing the operators with their
+/// inverses. Only few operators can be undone for a given target, e.g. `*`
+/// needs the target to be divisible and `||` needs the target to end with the
+/// digits of the value, so most combinations are never tried.
+fn solve_backwards(target: i64, values: &[i64], operators: &[Operator]) -> Option<Vec<Operator>> {
+    let (&last, rest) = values.split_last()?;
+    if rest.is_empty() {
+        return (target == last).then(Vec::new);
+    }
+
+    operators.iter().find_map(|operator| {
+        let mut solution = match (operator.inverse?)(target, last) {
+            Inverse::None => return None,
+            Inverse::One(left) => solve_backwards(left, rest, operators)?,
+            // Whatever comes before works, as long as it can be evaluated
+            Inverse::Any => solve_forwards(rest[0], &rest[1..], operators, &|_| true)?,
+        };
+        solution.push(*operator);
+        Some(solution)
+    })
+}
+
+fn parse_line(line: &str) -> Option<(i64, Vec<i64>)> {
+    let (answer, values) = line.split_once(':')?;
+    let answer = answer.parse::<i64>().ok()?;
+    let values = values
+        .split_whitespace()
+        .map(|x| x.parse::<i64>().ok().filter(|&x| x >= 0))
+        .collect::<Option<Vec<i64>>>()?;
+
+    if values.is_empty() {
+        return None;
+    }
+
+    Some((answer, values))
+}
+
+/// Sums the answers of all equations that can be made true, or returns `None`
+/// if the input is malformed or an equation has too many operator combinations.
+fn total_calibration_result(input: &str, operators: &[Operator]) -> Option<i64> {
+    let mut result: i64 = 0;
+    for line in input.lines() {
+        let (answer, values) = parse_line(line)?;
+
+        if solve_equation(answer, &values, operators).ok()?.is_some() {
+            result = result.checked_add(answer)?;
+        }
+    }
+
+    Some(result)
+}
+
+/// Shows how every equation in the puzzle input can be made true with the
+/// given operators, one equation per line, e.g. `292 = 11 + 6 * 16 + 20`.
+///
+/// Equations without a solution are shown as they are in the input, followed
+/// by `(no solution)`. The last line is the total calibration result. Returns
+/// `None` if the input cannot be parsed or an equation has too many operator
+/// combinations, like the puzzle answers.
+pub fn explain(input: &str, operators: &[Operator]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut total: i64 = 0;
+
+    for line in input.lines() {
+        let (answer, values) = parse_line(line)?;
+
+        match solve_equation(answer, &values, operators).ok()? {
+            Some(solution) => {
+                let mut expression = values[0].to_string();
+                for (operator, value) in solution.iter().zip(&values[1..]) {
+                    expression.push_str(&format!(" {} {}", operator.symbol(), value));
+                }
+                lines.push(format!("{} = {}", answer, expression));
+                total = total.checked_add(answer)?;
             }
+            None => lines.push(format!("{} (no solution)", line)),
         }
-    } else if current_value == goal {
-        results.insert(current_value);
     }
+
+    lines.push(format!("Total calibration result: {}", total));
+
+    Some(lines.join("\n"))
 }
 
 impl Puzzle for Day7 {
     fn execute_part_1(&mut self, input: &str) {
-        let mut result = 0;
-        for line in input.lines() {
-            let mut splitted_line = line.split(":");
-            let answer = splitted_line.next().unwrap().parse::<i64>().unwrap();
-            let values = splitted_line
-                .next()
-                .unwrap()
-                .split_whitespace()
-                .map(|x| x.parse::<i64>().unwrap())
-                .collect::<Vec<i64>>();
-
-            let mut results: HashSet<i64> = HashSet::new();
-            let (current_value, remaining) = values.split_first().unwrap();
-
-            calibration_equation(
-                *current_value,
-                remaining,
-                Operator::Add,
-                &mut results,
-                answer,
-                false,
-            );
-
-            calibration_equation(
-                *current_value,
-                remaining,
-                Operator::Mul,
-                &mut results,
-                answer,
-                false,
-            );
-
-            if results.contains(&answer) {
-                result += answer;
-            }
-        }
-        self.part_1_result = Some(result.to_string());
+        self.part_1_result =
+            total_calibration_result(input, &self.part_1_operators).map(|x| x.to_string());
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let mut result = 0;
-        for line in input.lines() {
-            let mut splitted_line = line.split(":");
-            let answer = splitted_line.next().unwrap().parse::<i64>().unwrap();
-            let values = splitted_line
-                .next()
-                .unwrap()
-                .split_whitespace()
-                .map(|x| x.parse::<i64>().unwrap())
-                .collect::<Vec<i64>>();
-
-            let mut results: HashSet<i64> = HashSet::new();
-            let (current_value, remaining) = values.split_first().unwrap();
-
-            calibration_equation(
-                *current_value,
-                remaining,
-                Operator::Add,
-                &mut results,
-                answer,
-                true,
-            );
-
-            calibration_equation(
-                *current_value,
-                remaining,
-                Operator::Mul,
-                &mut results,
-                answer,
-                true,
-            );
-
-            calibration_equation(
-                *current_value,
-                remaining,
-                Operator::Con,
-                &mut results,
-                answer,
-                true,
-            );
-
-            if results.contains(&answer) {
-                result += answer;
-            }
-        }
-        self.part_2_result = Some(result.to_string());
+        self.part_2_result =
+            total_calibration_result(input, &self.part_2_operators).map(|x| x.to_string());
     }
 
     fn get_result_part_1(&self) -> Option<String> {
@@ -219,4 +378,80 @@ mod tests {
         let result = puzzle.get_result_part_2();
         assert_eq!(result, Some("264184041398847".to_string()));
     }
+
+    #[test]
+    fn test_day7_explain() {
+        let input = read_test_input(7, None, None);
+
+        let explanation = explain(&input, PART_2_OPERATORS).unwrap();
+
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines[0], "190 = 10 * 19");
+        assert_eq!(lines[2], "83: 17 5 (no solution)");
+        assert_eq!(lines[4], "7290 = 6 * 8 || 6 * 15");
+        assert_eq!(lines[8], "292 = 11 + 6 * 16 + 20");
+        assert_eq!(lines[9], "Total calibration result: 11387");
+    }
+
+    #[test]
+    fn test_day7_custom_operators() {
+        let mut puzzle = Day7::with_operators(&[Operator::SUB, Operator::XOR], &[]);
+        let input = "3: 10 4 3\n11: 10 4 3\n5: 10 4 3\n100: 10 4 3";
+
+        puzzle.execute_part_1(input);
+
+        let result = puzzle.get_result_part_1();
+        assert_eq!(result, Some("19".to_string()));
+    }
+
+    #[test]
+    fn test_day7_operators_without_inverse() {
+        let operators: Vec<Operator> = ["+", "-", "/", "**"]
+            .into_iter()
+            .map(|symbol| Operator::from_symbol(symbol).unwrap())
+            .collect();
+        let input = "81: 3 2 2\n4: 20 4 1\n2: 7 2";
+
+        let explanation = explain(input, &operators).unwrap();
+
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "81 = 3 ** 2 ** 2",
+                "4 = 20 / 4 - 1",
+                "2: 7 2 (no solution)",
+                "Total calibration result: 85",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_day7_too_many_combinations() {
+        let mut puzzle = Day7::with_operators(PART_1_OPERATORS, BUILT_IN_OPERATORS);
+        let input = "26: 1 1 1 1 1 1 1 1 1 1 1 1 1 13\n3: 1 2";
+
+        puzzle.execute_part_1(input);
+        puzzle.execute_part_2(input);
+
+        // 2^13 combinations with two operators, but 7^13 with all of them
+        assert_eq!(puzzle.get_result_part_1(), Some("29".to_string()));
+        assert_eq!(puzzle.get_result_part_2(), None);
+        assert!(explain(input, PART_1_OPERATORS).is_some());
+        assert_eq!(explain(input, BUILT_IN_OPERATORS), None);
+    }
+
+    #[test]
+    fn test_day7_multiply_by_zero() {
+        let mut puzzle = Day7::new();
+        let input = "0: 7 5 0\n0: 7 0 5";
+
+        puzzle.execute_part_1(input);
+
+        assert_eq!(puzzle.get_result_part_1(), Some("0".to_string()));
+        assert_eq!(
+            explain(input, PART_1_OPERATORS).unwrap().lines().next(),
+            Some("0 = 7 + 5 * 0")
+        );
+    }
 }
@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::adventofcode::Puzzle;
+use crate::grid::{parse_grid, Grid};
 
+#[derive(Default)]
 pub struct Day8 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -67,9 +69,14 @@ fn opposite_direction_points_part_2(
 
 impl Puzzle for Day8 {
     fn execute_part_1(&mut self, input: &str) {
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let char_map: Vec<char> = input.lines().collect::<String>().chars().collect();
+        let Some(Grid {
+            tiles: char_map,
+            width,
+            height,
+        }) = parse_grid(input)
+        else {
+            return;
+        };
         let mut antinodes: HashSet<(i32, i32)> = HashSet::new();
 
         let antennas: HashMap<char, Vec<(i32, i32)>> = char_map
@@ -120,9 +127,14 @@ impl Puzzle for Day8 {
     }
 
     fn execute_part_2(&mut self, input: &str) {
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let char_map: Vec<char> = input.lines().collect::<String>().chars().collect();
+        let Some(Grid {
+            tiles: char_map,
+            width,
+            height,
+        }) = parse_grid(input)
+        else {
+            return;
+        };
         let mut antinodes: HashSet<(i32, i32)> = HashSet::new();
 
         let antennas: HashMap<char, Vec<(i32, i32)>> = char_map
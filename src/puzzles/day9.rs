@@ -1,10 +1,9 @@
-use std::{
-    cmp::Ordering,
-    collections::{BTreeMap, VecDeque},
-};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day9 {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
@@ -19,222 +18,149 @@ impl Day9 {
     }
 }
 
-type FileId = usize;
-type FileSize = u32;
-
-impl Puzzle for Day9 {
-    fn execute_part_1(&mut self, input: &str) {
-        let mut files: Vec<(usize, Option<FileId>, FileSize)> = input
-            .trim()
-            .chars()
-            .enumerate() // Add the original index
-            .filter(|(idx, _)| idx % 2 == 0) // Keep only every second character
-            .enumerate() // Add the filtered index
-            .map(|(file_id, (original_idx, c))| {
-                (original_idx, Some(file_id), c.to_digit(10).unwrap())
-            })
-            .collect();
-
-        let mut free_space: VecDeque<(usize, FileSize)> = input
-            .trim()
-            .chars()
-            .enumerate()
-            .filter(|(idx, _)| idx % 2 != 0) // Keep odd-indexed chars
-            .map(|(index, c)| (index, c.to_digit(10).unwrap()))
-            .collect();
-
-        let mut files_filled_in_freed_area: Vec<(usize, Option<FileId>, FileSize)> = Vec::new();
-        for (file_index, file_id, file_size) in files.iter_mut().rev() {
-            while *file_size != 0 {
-                if let Some(current_free_block) = free_space.pop_front() {
-                    let (free_index, mut free_size) = current_free_block;
-
-                    if free_index > *file_index {
-                        // Only handle freed indexes after the current file
-                        break;
-                    }
-
-                    match file_size.cmp(&&mut free_size) {
-                        Ordering::Greater => {
-                            // The file is larger than this free space can hold.
-
-                            // Done! Push this new (prev freed) block into a separate list.
-                            files_filled_in_freed_area.push((free_index, *file_id, free_size));
-
-                            *file_size -= free_size; // Update the current file with the remaning size.
-                        }
-                        Ordering::Equal => {
-                            // Perfect fit!
-
-                            // Done! Push this new (prev freed) block into a separate list.
-                            files_filled_in_freed_area.push((free_index, *file_id, *file_size));
-
-                            // Size 0 means this block is no longer applicable.
-                            *file_size = 0;
-                        }
-                        Ordering::Less => {
-                            // The smaller is larger than this free space can hold.
-                            // So take a chunk of it and return the free block to the beginning of the list.
+/// A run of consecutive blocks on the disk.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    start: u64,
+    size: u64,
+}
 
-                            let available_size_left = free_size - *file_size;
+/// The files, indexed by their ID, and the free spans that follow each of them.
+struct Disk {
+    files: Vec<Span>,
+    free: Vec<Span>,
+}
 
-                            // Split the free space into two chunks.
+/// Reads the disk map digits, or `None` if it contains anything else.
+fn parse_input(input: &str) -> Option<Disk> {
+    let mut disk = Disk {
+        files: Vec::new(),
+        free: Vec::new(),
+    };
+    let mut position = 0;
+
+    for (index, c) in input.trim().chars().enumerate() {
+        let span = Span {
+            start: position,
+            size: u64::from(c.to_digit(10)?),
+        };
+        if index % 2 == 0 {
+            disk.files.push(span);
+        } else {
+            disk.free.push(span);
+        }
+        position += span.size;
+    }
 
-                            // First, push this new (prev freed) block into a separate list.
-                            files_filled_in_freed_area.push((free_index, *file_id, *file_size));
+    Some(disk)
+}
 
-                            // Second, give back the free block into the beginning of the queue
-                            free_space.push_front((free_index, available_size_left));
+/// The checksum of `size` blocks of file `id` starting at `start`, without
+/// visiting the blocks one by one.
+fn checksum(id: usize, start: u64, size: u64) -> u128 {
+    // The positions add up to `size * start + (0 + 1 + ... + size - 1)`
+    let positions = size * start + size * size.saturating_sub(1) / 2;
+    id as u128 * u128::from(positions)
+}
 
-                            // Size 0 means this block is no longer applicable.
-                            *file_size = 0;
-                        }
-                    }
-                } else {
-                    // No more free spaces
-                    break;
-                }
+impl Disk {
+    /// Moves single blocks from the end of the disk into the leftmost free
+    /// spaces and returns the resulting checksum.
+    ///
+    /// Walks the files from the left while draining the files from the right,
+    /// so every file contributes at most a few spans to the checksum.
+    fn compact_blocks(&self) -> u128 {
+        let Some(mut last) = self.files.len().checked_sub(1) else {
+            return 0;
+        };
+        let mut remaining = self.files[last].size;
+        let mut result = 0;
+
+        for (id, file) in self.files.iter().enumerate() {
+            if id == last {
+                // Whatever has not been moved out of the last file stays in place
+                result += checksum(id, file.start, remaining);
+                break;
             }
-        }
-
-        let highest_index = std::cmp::max(
-            files.last().unwrap().0,
-            files_filled_in_freed_area.last().unwrap().0,
-        );
-
-        let mut disk_map = Vec::new();
-        for file_index in 0..highest_index {
-            let relevant_files: Vec<_> = files
-                .iter()
-                .chain(&files_filled_in_freed_area)
-                .filter(|&&(i, _, _)| i == file_index)
-                .collect();
-
-            for (_index, id, size) in relevant_files.iter() {
-                if let Some(id) = id {
-                    for value in std::iter::repeat(id).take(*size as usize) {
-                        disk_map.push(*value);
-                    }
+            result += checksum(id, file.start, file.size);
+
+            let Some(free) = self.free.get(id) else {
+                break;
+            };
+            let (mut position, mut space) = (free.start, free.size);
+            while space > 0 && last > id {
+                let moved = space.min(remaining);
+                result += checksum(last, position, moved);
+                position += moved;
+                space -= moved;
+                remaining -= moved;
+
+                if remaining == 0 {
+                    last -= 1;
+                    remaining = self.files[last].size;
                 }
             }
+            if last == id {
+                break;
+            }
         }
 
-        let res: usize = disk_map
-            .iter()
-            .enumerate()
-            .map(|(index, value)| index * value)
-            .sum();
-
-        self.part_1_result = Some(res.to_string());
+        result
     }
 
-    fn execute_part_2(&mut self, input: &str) {
-        let files: Vec<(usize, Option<FileId>, FileSize)> = input
-            .trim()
-            .chars()
-            .enumerate() // Add the original index
-            .filter(|(idx, _)| idx % 2 == 0) // Keep only every second character
-            .enumerate() // Add the filtered index
-            .map(|(file_id, (original_idx, c))| {
-                (original_idx, Some(file_id), c.to_digit(10).unwrap())
-            })
-            .collect();
-
-        let free_space: VecDeque<(usize, Option<FileId>, FileSize)> = input
-            .trim()
-            .chars()
-            .enumerate()
-            .filter(|(idx, _)| idx % 2 != 0) // Keep odd-indexed chars
-            .map(|(index, c)| (index, None, c.to_digit(10).unwrap()))
-            .collect();
-
-        let mut disk_map_map: BTreeMap<usize, Vec<(Option<FileId>, FileSize)>> = BTreeMap::new();
-        for (idx, file_id, file_size) in files.iter() {
-            disk_map_map
-                .entry(*idx)
-                .or_default()
-                .push((*file_id, *file_size));
-        }
-
-        for (idx, _, free_space) in free_space.iter() {
-            disk_map_map
-                .entry(*idx)
-                .or_default()
-                .push((None, *free_space));
+    /// Moves whole files, from the highest ID down, into the leftmost free
+    /// span that fits them and returns the resulting checksum.
+    ///
+    /// The free spans are kept in one min-heap per size, ordered by position.
+    /// Files are at most 9 blocks, so the leftmost fitting span is the best of
+    /// at most 9 heap tops. Space freed by a moved file is never reused, since
+    /// every file that is still to be moved lies further to the left.
+    fn compact_files(&self) -> u128 {
+        let mut heaps: [BinaryHeap<Reverse<u64>>; 10] = Default::default();
+        for free in self.free.iter().filter(|free| free.size > 0) {
+            heaps[free.size as usize].push(Reverse(free.start));
         }
 
-        for (file_index, file_id, file_size) in files.iter().rev() {
-            let next_first_free_space = disk_map_map
-                .iter_mut()
-                .filter(|(index, blocks)| {
-                    blocks
-                        .iter()
-                        .filter(|(id, size)| {
-                            id.is_none() && size >= file_size && index < &file_index
-                        })
-                        .count()
-                        != 0
-                })
-                .min_by_key(|(index, _)| **index);
-
-            let index_to_remove;
-            if let Some((index, blocks)) = next_first_free_space {
-                if index >= file_index {
-                    break;
+        let mut result = 0;
+        for (id, file) in self.files.iter().enumerate().rev() {
+            let target = (file.size as usize..heaps.len())
+                .filter_map(|size| heaps[size].peek().map(|&Reverse(start)| (start, size)))
+                .filter(|&(start, _)| start < file.start)
+                .min();
+
+            let start = match target {
+                Some((start, size)) if file.size > 0 => {
+                    heaps[size].pop();
+                    let left = size - file.size as usize;
+                    if left > 0 {
+                        heaps[left].push(Reverse(start + file.size));
+                    }
+                    start
                 }
+                _ => file.start,
+            };
+            result += checksum(id, start, file.size);
+        }
 
-                if let Some((free_block_id, free_block_size)) = blocks
-                    .iter_mut()
-                    .find(|(free_block_id, _)| free_block_id.is_none())
-                {
-                    match file_size.cmp(free_block_size) {
-                        Ordering::Greater => continue,
-                        Ordering::Equal => {
-                            *free_block_id = *file_id;
-                            *free_block_size = *file_size;
-
-                            index_to_remove = Some((*file_index, file_id.unwrap()));
-                        }
-                        Ordering::Less => {
-                            let available_size_left = *free_block_size - *file_size;
+        result
+    }
+}
 
-                            *free_block_id = *file_id;
-                            *free_block_size = *file_size;
+impl Puzzle for Day9 {
+    fn execute_part_1(&mut self, input: &str) {
+        let Some(disk) = parse_input(input) else {
+            return;
+        };
 
-                            blocks.push((None, available_size_left));
+        self.part_1_result = Some(disk.compact_blocks().to_string());
+    }
 
-                            index_to_remove = Some((*file_index, file_id.unwrap()));
-                        }
-                    }
-                    if let Some((index, file_id_to_be_removed)) = index_to_remove {
-                        let blocks = disk_map_map.get_mut(&index);
-                        if let Some(blocks) = blocks {
-                            blocks
-                                .iter_mut()
-                                .filter(|(file_id, _)| {
-                                    file_id.is_some() && file_id.unwrap() == file_id_to_be_removed
-                                })
-                                .for_each(|(file_id, _)| {
-                                    *file_id = None;
-                                });
-                        }
-                    }
-                }
-            }
-        }
+    fn execute_part_2(&mut self, input: &str) {
+        let Some(disk) = parse_input(input) else {
+            return;
+        };
 
-        let res: usize = disk_map_map
-            .iter()
-            .flat_map(|(_, list)| {
-                list.iter().flat_map(|(file_id, file_size)| {
-                    std::iter::repeat(file_id.unwrap_or(0)).take(*file_size as usize)
-                })
-            })
-            .enumerate()
-            .map(|(index, value)| index * value)
-            .sum();
-
-        self.part_2_result = Some(res.to_string());
+        self.part_2_result = Some(disk.compact_files().to_string());
     }
 
     fn get_result_part_1(&self) -> Option<String> {
@@ -1,5 +1,6 @@
 use crate::adventofcode::Puzzle;
 
+#[derive(Default)]
 pub struct Day<X> {
     part_1_result: Option<String>,
     part_2_result: Option<String>,
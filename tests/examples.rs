@@ -0,0 +1,130 @@
+//! Data-driven tests for the puzzle examples.
+//!
+//! Every entry in `inputs/examples.toml` is turned into one named test case
+//! per part, e.g. `day10::test_day_10_5.txt::part2`, so the usual test
+//! filtering works: `cargo test --test examples -- day10`.
+//!
+//! Examples that need a puzzle created differently than for the puzzle input,
+//! like the smaller bathroom of day 14, list the parameters it is created with.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc2024::puzzles::{day14, day18, day20};
+use aoc2024::Puzzle;
+use libtest_mimic::{Arguments, Failed, Trial};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    example: Vec<Example>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Example {
+    day: u32,
+    file: String,
+    part1: Option<String>,
+    part2: Option<String>,
+    #[serde(default)]
+    params: Params,
+}
+
+/// The arguments for the `with_*` constructors of the days that have them.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Params {
+    width: Option<i64>,
+    height: Option<i64>,
+    size: Option<i32>,
+    bytes: Option<usize>,
+    threshold: Option<usize>,
+}
+
+fn inputs_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs")
+}
+
+fn load_manifest() -> Manifest {
+    let path = inputs_dir().join("examples.toml");
+    let content = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("Failed to read manifest '{}': {}", path.display(), err));
+
+    toml::from_str(&content)
+        .unwrap_or_else(|err| panic!("Failed to parse manifest '{}': {}", path.display(), err))
+}
+
+fn create_puzzle(day: u32, params: Params) -> Result<Box<dyn Puzzle>, String> {
+    let Params {
+        width,
+        height,
+        size,
+        bytes,
+        threshold,
+    } = params;
+
+    let puzzle: Box<dyn Puzzle> = match (day, width, height, size, bytes, threshold) {
+        (14, Some(width), Some(height), None, None, None) => {
+            Box::new(day14::Day14::with_size(width, height))
+        }
+        (18, None, None, Some(size), Some(bytes), None) => {
+            Box::new(day18::Day18::with_size(size, bytes))
+        }
+        (20, None, None, None, None, Some(threshold)) => {
+            Box::new(day20::Day20::with_threshold(threshold))
+        }
+        (day, None, None, None, None, None) => aoc2024::puzzles::create(day)
+            .ok_or_else(|| format!("day {} is not implemented", day))?,
+        _ => return Err(format!("day {} does not take {:?}", day, params)),
+    };
+
+    Ok(puzzle)
+}
+
+fn run_example(
+    day: u32,
+    part: u32,
+    params: Params,
+    path: &Path,
+    expected: &str,
+) -> Result<(), Failed> {
+    let input = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read input file '{}': {}", path.display(), err))?;
+
+    let mut puzzle = create_puzzle(day, params)?;
+    let answer =
+        aoc2024::solve_puzzle(puzzle.as_mut(), part, &input).map_err(|err| err.to_string())?;
+    if answer != expected {
+        return Err(format!("expected '{}', got '{}'", expected, answer).into());
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args = Arguments::from_args();
+
+    let trials = load_manifest()
+        .example
+        .into_iter()
+        .flat_map(|example| {
+            let path = inputs_dir().join(&example.file);
+
+            [(1, example.part1), (2, example.part2)]
+                .into_iter()
+                .filter_map(move |(part, expected)| {
+                    let expected = expected?;
+                    let name = format!("day{}::{}::part{}", example.day, example.file, part);
+                    let path = path.clone();
+                    let day = example.day;
+                    let params = example.params;
+
+                    Some(Trial::test(name, move || {
+                        run_example(day, part, params, &path, &expected)
+                    }))
+                })
+        })
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}
@@ -0,0 +1,60 @@
+//! Replays the inputs that once crashed a fuzz target.
+//!
+//! Every file in `inputs/fuzz/` is named `day_XX_<kind>_<hash>.txt` and is
+//! run through both parts of that day. A test case passes as long as the
+//! solver returns, whether or not it produced an answer.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use libtest_mimic::{Arguments, Failed, Trial};
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("inputs")
+        .join("fuzz")
+}
+
+fn parse_day(file_name: &str) -> Option<u32> {
+    file_name.strip_prefix("day_")?.get(..2)?.parse().ok()
+}
+
+fn run_fixture(day: u32, path: &Path) -> Result<(), Failed> {
+    let bytes = fs::read(path)
+        .map_err(|err| format!("Failed to read fixture '{}': {}", path.display(), err))?;
+    // The fuzz targets take `&str` input, which `Arbitrary` builds from the
+    // longest valid UTF-8 prefix of the raw bytes.
+    let input = match std::str::from_utf8(&bytes) {
+        Ok(input) => input,
+        Err(err) => std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap(),
+    };
+
+    for part in [1, 2] {
+        let _ = aoc2024::solve(day, part, input);
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args = Arguments::from_args();
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .expect("Failed to read fuzz fixtures directory")
+        .map(|entry| entry.expect("Failed to read fixture entry").path())
+        .collect();
+    paths.sort();
+
+    let trials = paths
+        .into_iter()
+        .map(|path| {
+            let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+            let day = parse_day(&file_name)
+                .unwrap_or_else(|| panic!("Fixture '{}' does not name a day", file_name));
+
+            Trial::test(file_name, move || run_fixture(day, &path))
+        })
+        .collect();
+
+    libtest_mimic::run(&args, trials).exit();
+}
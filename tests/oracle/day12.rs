@@ -0,0 +1,132 @@
+use proptest::prelude::*;
+
+use crate::render_grid;
+
+/// A garden of up to 10x10 plots using only a few plant types, so that
+/// regions get irregular shapes and enclose each other.
+fn garden() -> impl Strategy<Value = Vec<Vec<char>>> {
+    (1..10usize, 1..10usize).prop_flat_map(|(width, height)| {
+        prop::collection::vec(
+            prop::collection::vec(prop::sample::select(vec!['A', 'B', 'C']), width),
+            height,
+        )
+    })
+}
+
+/// Labels every plot with the index of its region.
+fn label_regions(grid: &[Vec<char>]) -> Vec<Vec<usize>> {
+    let height = grid.len();
+    let width = grid[0].len();
+    let mut labels = vec![vec![usize::MAX; width]; height];
+    let mut next_label = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            if labels[y][x] != usize::MAX {
+                continue;
+            }
+
+            let mut stack = vec![(x, y)];
+            labels[y][x] = next_label;
+            while let Some((x, y)) = stack.pop() {
+                let neighbours = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbours {
+                    if nx < width
+                        && ny < height
+                        && labels[ny][nx] == usize::MAX
+                        && grid[ny][nx] == grid[y][x]
+                    {
+                        labels[ny][nx] = next_label;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            next_label += 1;
+        }
+    }
+
+    labels
+}
+
+fn naive_part_1(grid: &[Vec<char>]) -> usize {
+    let labels = label_regions(grid);
+    let region_count = labels.iter().flatten().max().unwrap() + 1;
+    let mut areas = vec![0; region_count];
+    let mut perimeters = vec![0; region_count];
+
+    let label_at = |x: i32, y: i32| {
+        labels
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+    };
+
+    for (y, row) in labels.iter().enumerate() {
+        for (x, &label) in row.iter().enumerate() {
+            let (x, y) = (x as i32, y as i32);
+            areas[label] += 1;
+            perimeters[label] += [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .iter()
+                .filter(|&&(nx, ny)| label_at(nx, ny) != Some(label))
+                .count();
+        }
+    }
+
+    areas.iter().zip(&perimeters).map(|(a, p)| a * p).sum()
+}
+
+/// Counts sides by walking along every fence: a fence segment starts a new
+/// side unless the plot before it along the same line has the same fence.
+fn naive_part_2(grid: &[Vec<char>]) -> usize {
+    let labels = label_regions(grid);
+    let region_count = labels.iter().flatten().max().unwrap() + 1;
+    let mut areas = vec![0; region_count];
+    let mut sides = vec![0; region_count];
+
+    let label_at = |x: i32, y: i32| {
+        labels
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+    };
+    let has_fence = |x: i32, y: i32, (dx, dy): (i32, i32)| {
+        label_at(x, y).is_some() && label_at(x + dx, y + dy) != label_at(x, y)
+    };
+
+    for (y, row) in labels.iter().enumerate() {
+        for (x, &label) in row.iter().enumerate() {
+            let (x, y) = (x as i32, y as i32);
+            areas[label] += 1;
+
+            for (dx, dy) in [(0i32, -1i32), (0, 1), (-1, 0), (1, 0)] {
+                // The plot before this one along the fence line
+                let (px, py) = (x - dy.abs(), y - dx.abs());
+                let continues_side = label_at(px, py) == Some(label) && has_fence(px, py, (dx, dy));
+                if has_fence(x, y, (dx, dy)) && !continues_side {
+                    sides[label] += 1;
+                }
+            }
+        }
+    }
+
+    areas.iter().zip(&sides).map(|(a, s)| a * s).sum()
+}
+
+proptest! {
+    #[test]
+    fn test_day12_part_1_oracle(grid in garden()) {
+        let answer = aoc2024::solve(12, 1, &render_grid(&grid)).unwrap();
+        prop_assert_eq!(answer, naive_part_1(&grid).to_string());
+    }
+
+    #[test]
+    fn test_day12_part_2_oracle(grid in garden()) {
+        let answer = aoc2024::solve(12, 2, &render_grid(&grid)).unwrap();
+        prop_assert_eq!(answer, naive_part_2(&grid).to_string());
+    }
+}
@@ -0,0 +1,121 @@
+use proptest::prelude::*;
+
+/// Page ordering rules covering every pair of pages, derived from a random
+/// total order, plus updates picked from those pages.
+#[derive(Debug, Clone)]
+struct PrintQueue {
+    rules: Vec<(u32, u32)>,
+    updates: Vec<Vec<u32>>,
+}
+
+fn print_queue() -> impl Strategy<Value = PrintQueue> {
+    prop::sample::subsequence((10..100u32).collect::<Vec<_>>(), 3..20)
+        .prop_shuffle()
+        .prop_flat_map(|order| {
+            let rules: Vec<(u32, u32)> = order
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &before)| order[i + 1..].iter().map(move |&after| (before, after)))
+                .collect();
+            let update = prop::sample::subsequence(order.clone(), 3..=order.len())
+                .prop_filter("updates have a middle page", |pages| pages.len() % 2 == 1)
+                .prop_shuffle();
+
+            (
+                Just(rules).prop_shuffle(),
+                prop::collection::vec(update, 1..10),
+            )
+        })
+        .prop_map(|(rules, updates)| PrintQueue { rules, updates })
+}
+
+/// Like [`print_queue`], but with some of the rules left out, so the order of
+/// some pages in the updates is not decided by any rule.
+fn partial_print_queue() -> impl Strategy<Value = PrintQueue> {
+    print_queue().prop_flat_map(|queue| {
+        let count = queue.rules.len();
+        (
+            prop::sample::subsequence(queue.rules, 0..=count),
+            Just(queue.updates),
+        )
+            .prop_map(|(rules, updates)| PrintQueue { rules, updates })
+    })
+}
+
+fn render(queue: &PrintQueue) -> String {
+    let rules: Vec<String> = queue
+        .rules
+        .iter()
+        .map(|(before, after)| format!("{}|{}", before, after))
+        .collect();
+    let updates: Vec<String> = queue
+        .updates
+        .iter()
+        .map(|pages| {
+            let pages: Vec<String> = pages.iter().map(|page| page.to_string()).collect();
+            pages.join(",")
+        })
+        .collect();
+
+    format!("{}\n\n{}\n", rules.join("\n"), updates.join("\n"))
+}
+
+/// Orders the pages by counting how many of them must come before each one.
+fn naive_order(rules: &[(u32, u32)], pages: &[u32]) -> Vec<u32> {
+    let mut ordered = pages.to_vec();
+    ordered.sort_by_key(|page| {
+        pages
+            .iter()
+            .filter(|other| rules.contains(&(**other, *page)))
+            .count()
+    });
+    ordered
+}
+
+fn naive(queue: &PrintQueue, reordered: bool) -> u32 {
+    queue
+        .updates
+        .iter()
+        .filter_map(|pages| {
+            let ordered = naive_order(&queue.rules, pages);
+            (reordered == (&ordered != pages)).then(|| ordered[ordered.len() / 2])
+        })
+        .sum()
+}
+
+/// Sums the middle pages of the updates where no page comes after a page that
+/// has to come after it.
+fn naive_in_order(queue: &PrintQueue) -> u32 {
+    queue
+        .updates
+        .iter()
+        .filter(|pages| {
+            pages.iter().enumerate().all(|(i, &first)| {
+                pages[i + 1..]
+                    .iter()
+                    .all(|&second| !queue.rules.contains(&(second, first)))
+            })
+        })
+        .map(|pages| pages[pages.len() / 2])
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn test_day5_part_1_oracle(queue in print_queue()) {
+        let answer = aoc2024::solve(5, 1, &render(&queue)).unwrap();
+        prop_assert_eq!(answer, naive(&queue, false).to_string());
+    }
+
+    #[test]
+    fn test_day5_part_1_partial_rules_oracle(queue in partial_print_queue()) {
+        let answer = aoc2024::solve(5, 1, &render(&queue)).unwrap();
+        prop_assert_eq!(answer, naive_in_order(&queue).to_string());
+    }
+
+    #[test]
+    fn test_day5_part_2_oracle(queue in print_queue()) {
+        let answer = aoc2024::solve(5, 2, &render(&queue)).unwrap();
+        prop_assert_eq!(answer, naive(&queue, true).to_string());
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+
+use crate::render_grid;
+
+/// A lab map of up to 12x12 cells with scattered obstacles and one guard facing north.
+fn lab_map() -> impl Strategy<Value = Vec<Vec<char>>> {
+    (2..12usize, 2..12usize)
+        .prop_flat_map(|(width, height)| {
+            (
+                prop::collection::vec(
+                    prop::collection::vec(prop::bool::weighted(0.15), width),
+                    height,
+                ),
+                0..width,
+                0..height,
+            )
+        })
+        .prop_map(|(obstacles, guard_x, guard_y)| {
+            let mut grid: Vec<Vec<char>> = obstacles
+                .iter()
+                .map(|row| row.iter().map(|&o| if o { '#' } else { '.' }).collect())
+                .collect();
+            grid[guard_y][guard_x] = '^';
+            grid
+        })
+}
+
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Walks the guard one step at a time. Returns the visited cells, or `None`
+/// if the guard ends up in a loop.
+fn walk(grid: &[Vec<char>], extra_obstacle: Option<(i32, i32)>) -> Option<HashSet<(i32, i32)>> {
+    let height = grid.len() as i32;
+    let width = grid[0].len() as i32;
+    let is_blocked =
+        |x: i32, y: i32| grid[y as usize][x as usize] == '#' || extra_obstacle == Some((x, y));
+
+    let mut position = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .find(|&(x, y)| grid[y as usize][x as usize] == '^')
+        .unwrap();
+    let mut direction = 0;
+    let mut states = HashSet::new();
+
+    loop {
+        if !states.insert((position, direction)) {
+            return None;
+        }
+
+        let (dx, dy) = DIRECTIONS[direction];
+        let (x, y) = (position.0 + dx, position.1 + dy);
+        if x < 0 || x >= width || y < 0 || y >= height {
+            return Some(states.into_iter().map(|(position, _)| position).collect());
+        }
+
+        if is_blocked(x, y) {
+            direction = (direction + 1) % 4;
+        } else {
+            position = (x, y);
+        }
+    }
+}
+
+fn naive_part_2(grid: &[Vec<char>], path: &HashSet<(i32, i32)>) -> usize {
+    path.iter()
+        .filter(|&&(x, y)| grid[y as usize][x as usize] != '^')
+        .filter(|&&candidate| walk(grid, Some(candidate)).is_none())
+        .count()
+}
+
+proptest! {
+    #[test]
+    fn test_day6_part_1_oracle(grid in lab_map()) {
+        let path = walk(&grid, None);
+        prop_assume!(path.is_some());
+
+        let answer = aoc2024::solve(6, 1, &render_grid(&grid)).unwrap();
+        prop_assert_eq!(answer, path.unwrap().len().to_string());
+    }
+
+    #[test]
+    fn test_day6_part_2_oracle(grid in lab_map()) {
+        let path = walk(&grid, None);
+        prop_assume!(path.is_some());
+
+        let answer = aoc2024::solve(6, 2, &render_grid(&grid)).unwrap();
+        prop_assert_eq!(answer, naive_part_2(&grid, &path.unwrap()).to_string());
+    }
+}
@@ -0,0 +1,80 @@
+use proptest::prelude::*;
+
+/// A single calibration equation. Half of the generated equations are built
+/// from a random operator sequence so that they are guaranteed solvable.
+fn equation() -> impl Strategy<Value = (i64, Vec<i64>)> {
+    (
+        prop::collection::vec(1..=99i64, 1..=6),
+        prop::collection::vec(0..3u8, 5),
+        prop::bool::ANY,
+        1..=10_000i64,
+    )
+        .prop_map(|(values, operators, solvable, random_target)| {
+            let target = if solvable {
+                values[1..]
+                    .iter()
+                    .zip(&operators)
+                    .fold(values[0], |acc, (&value, &operator)| {
+                        apply(acc, value, operator)
+                    })
+            } else {
+                random_target
+            };
+            (target, values)
+        })
+}
+
+fn apply(left: i64, right: i64, operator: u8) -> i64 {
+    match operator {
+        0 => left + right,
+        1 => left * right,
+        _ => format!("{}{}", left, right).parse().unwrap(),
+    }
+}
+
+/// Tries every combination of operators from left to right.
+fn is_solvable(target: i64, values: &[i64], operator_count: u8) -> bool {
+    let combinations = (operator_count as u32).pow(values.len() as u32 - 1);
+
+    (0..combinations).any(|mut combination| {
+        let result = values[1..].iter().fold(values[0], |acc, &value| {
+            let operator = (combination % operator_count as u32) as u8;
+            combination /= operator_count as u32;
+            apply(acc, value, operator)
+        });
+        result == target
+    })
+}
+
+fn render(equations: &[(i64, Vec<i64>)]) -> String {
+    equations
+        .iter()
+        .map(|(target, values)| {
+            let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+            format!("{}: {}", target, values.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn naive(equations: &[(i64, Vec<i64>)], operator_count: u8) -> i64 {
+    equations
+        .iter()
+        .filter(|(target, values)| is_solvable(*target, values, operator_count))
+        .map(|(target, _)| target)
+        .sum()
+}
+
+proptest! {
+    #[test]
+    fn test_day7_part_1_oracle(equations in prop::collection::vec(equation(), 1..20)) {
+        let answer = aoc2024::solve(7, 1, &render(&equations)).unwrap();
+        prop_assert_eq!(answer, naive(&equations, 2).to_string());
+    }
+
+    #[test]
+    fn test_day7_part_2_oracle(equations in prop::collection::vec(equation(), 1..20)) {
+        let answer = aoc2024::solve(7, 2, &render(&equations)).unwrap();
+        prop_assert_eq!(answer, naive(&equations, 3).to_string());
+    }
+}
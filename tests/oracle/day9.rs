@@ -0,0 +1,97 @@
+use proptest::prelude::*;
+
+/// A disk map with files of 1-9 blocks separated by 0-9 free blocks.
+fn disk_map() -> impl Strategy<Value = String> {
+    prop::collection::vec((1..=9u32, 0..=9u32), 1..40).prop_map(|entries| {
+        let mut map: String = entries
+            .iter()
+            .flat_map(|(file, free)| [*file, *free])
+            .map(|digit| char::from_digit(digit, 10).unwrap())
+            .collect();
+        // The disk map always ends with a file
+        map.pop();
+        map
+    })
+}
+
+fn expand(disk_map: &str) -> Vec<Option<usize>> {
+    disk_map
+        .chars()
+        .enumerate()
+        .flat_map(|(index, c)| {
+            let id = (index % 2 == 0).then_some(index / 2);
+            std::iter::repeat_n(id, c.to_digit(10).unwrap() as usize)
+        })
+        .collect()
+}
+
+fn checksum(blocks: &[Option<usize>]) -> usize {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(position, id)| position * id.unwrap_or(0))
+        .sum()
+}
+
+fn naive_part_1(disk_map: &str) -> usize {
+    let mut blocks = expand(disk_map);
+    let mut left = 0;
+    let mut right = blocks.len() - 1;
+
+    while left < right {
+        if blocks[left].is_some() {
+            left += 1;
+        } else if blocks[right].is_none() {
+            right -= 1;
+        } else {
+            blocks.swap(left, right);
+        }
+    }
+
+    checksum(&blocks)
+}
+
+fn naive_part_2(disk_map: &str) -> usize {
+    let mut blocks = expand(disk_map);
+    let highest_id = (disk_map.len() - 1) / 2;
+
+    for id in (0..=highest_id).rev() {
+        let start = blocks.iter().position(|&block| block == Some(id)).unwrap();
+        let size = blocks[start..]
+            .iter()
+            .take_while(|&&block| block == Some(id))
+            .count();
+
+        let mut run = 0;
+        for position in 0..start {
+            if blocks[position].is_none() {
+                run += 1;
+                if run == size {
+                    let target = position + 1 - size;
+                    for offset in 0..size {
+                        blocks.swap(target + offset, start + offset);
+                    }
+                    break;
+                }
+            } else {
+                run = 0;
+            }
+        }
+    }
+
+    checksum(&blocks)
+}
+
+proptest! {
+    #[test]
+    fn test_day9_part_1_oracle(input in disk_map()) {
+        let answer = aoc2024::solve(9, 1, &input).unwrap();
+        prop_assert_eq!(answer, naive_part_1(&input).to_string());
+    }
+
+    #[test]
+    fn test_day9_part_2_oracle(input in disk_map()) {
+        let answer = aoc2024::solve(9, 2, &input).unwrap();
+        prop_assert_eq!(answer, naive_part_2(&input).to_string());
+    }
+}
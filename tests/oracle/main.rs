@@ -0,0 +1,21 @@
+//! Property-based tests comparing the optimized solvers against naive
+//! reference implementations.
+//!
+//! Every module generates random but valid puzzle inputs and checks that
+//! `aoc2024::solve` agrees with a straightforward brute-force solution.
+//! Failing inputs are shrunk by proptest and persisted to
+//! `tests/proptest-regressions/` so they are replayed on the next run.
+
+mod day12;
+mod day5;
+mod day6;
+mod day7;
+mod day9;
+
+/// Renders a grid of characters as puzzle input, one row per line.
+pub fn render_grid(grid: &[Vec<char>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}